@@ -1,44 +1,136 @@
 use crate::client::{BillplzClient, Environment};
 use crate::error::BillplzError;
-use crate::models::bank::{Bank, FpxBank};
-
-/// Returns the hardcoded list of Malaysian FPX banks.
-/// If the client environment is Staging, test banks are appended.
-fn production_banks() -> Vec<FpxBank> {
-    vec![
-        FpxBank { bank_code: "ABMB0212".into(), bank_name: "Alliance Bank".into() },
-        FpxBank { bank_code: "ABB0233".into(),  bank_name: "Affin Bank".into() },
-        FpxBank { bank_code: "AMBB0209".into(), bank_name: "AmBank".into() },
-        FpxBank { bank_code: "BCBB0235".into(), bank_name: "CIMB Clicks".into() },
-        FpxBank { bank_code: "BIMB0340".into(), bank_name: "Bank Islam".into() },
-        FpxBank { bank_code: "BKRM0602".into(), bank_name: "Bank Rakyat".into() },
-        FpxBank { bank_code: "BMMB0341".into(), bank_name: "Bank Muamalat".into() },
-        FpxBank { bank_code: "BSN0601".into(),  bank_name: "BSN".into() },
-        FpxBank { bank_code: "CIT0217".into(),  bank_name: "Citibank Berhad".into() },
-        FpxBank { bank_code: "HLB0224".into(),  bank_name: "Hong Leong Bank".into() },
-        FpxBank { bank_code: "HSBC0223".into(), bank_name: "HSBC Bank".into() },
-        FpxBank { bank_code: "KFH0346".into(),  bank_name: "Kuwait Finance House".into() },
-        FpxBank { bank_code: "MB2U0227".into(), bank_name: "Maybank2u".into() },
-        FpxBank { bank_code: "MBB0227".into(),  bank_name: "Maybank2E".into() },
-        FpxBank { bank_code: "MBB0228".into(),  bank_name: "Maybank2E".into() },
-        FpxBank { bank_code: "OCBC0229".into(), bank_name: "OCBC Bank".into() },
-        FpxBank { bank_code: "PBB0233".into(),  bank_name: "Public Bank".into() },
-        FpxBank { bank_code: "RHB0218".into(),  bank_name: "RHB Now".into() },
-        FpxBank { bank_code: "SCB0216".into(),  bank_name: "Standard Chartered".into() },
-        FpxBank { bank_code: "UOB0226".into(),  bank_name: "UOB Bank".into() },
-    ]
+use crate::models::bank::{Bank, BankVerificationResponse, FpxBank, FpxBankList};
+use crate::validation::{self, ValidationError};
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Known FPX bank codes and display names, used as a fallback when the live
+/// list cannot be fetched and to name the codes returned by the API.
+const PRODUCTION_BANKS: &[(&str, &str)] = &[
+    ("ABB0233", "Affin Bank"),
+    ("ABMB0212", "Alliance Bank"),
+    ("AGRO01", "AGRONet"),
+    ("AMBB0209", "AmBank"),
+    ("BCBB0235", "CIMB Clicks"),
+    ("BIMB0340", "Bank Islam"),
+    ("BKRM0602", "Bank Rakyat"),
+    ("BMMB0341", "Bank Muamalat"),
+    ("BOCM01", "Bank of China"),
+    ("BSN0601", "BSN"),
+    ("CIT0219", "Citibank Berhad"),
+    ("HLB0224", "Hong Leong Bank"),
+    ("HSBC0223", "HSBC Bank"),
+    ("KFH0346", "Kuwait Finance House"),
+    ("MB2U0227", "Maybank2u"),
+    ("MBB0228", "Maybank2E"),
+    ("OCBC0229", "OCBC Bank"),
+    ("PBB0233", "Public Bank"),
+    ("RHB0218", "RHB Now"),
+    ("SCB0216", "Standard Chartered"),
+    ("UOB0226", "UOB Bank"),
+];
+
+const STAGING_TEST_BANKS: &[(&str, &str)] = &[
+    ("TEST0001", "Test 0001"),
+    ("TEST0002", "Test 0002"),
+    ("TEST0003", "Test 0003"),
+    ("TEST0004", "Test 0004"),
+    ("TEST0021", "Test 0021"),
+    ("TEST0022", "Test 0022"),
+    ("TEST0023", "Test 0023"),
+];
+
+/// SWIFT codes of banks that accept payouts (mass payment instructions and
+/// payment orders) and bank account verification.
+const PAYOUT_BANKS: &[(&str, &str)] = &[
+    ("AGOBMYKL", "AgroBank"),
+    ("ARBKMYKL", "AmBank"),
+    ("BIMBMYKL", "Bank Islam"),
+    ("BKCHMYKL", "Bank of China"),
+    ("BKRMMYKL", "Bank Rakyat"),
+    ("BMMBMYKL", "Bank Muamalat"),
+    ("BSNAMYK1", "BSN"),
+    ("CIBBMYKL", "CIMB Bank"),
+    ("CITIMYKL", "Citibank"),
+    ("HBMBMYKL", "HSBC Bank"),
+    ("HLBBMYKL", "Hong Leong Bank"),
+    ("KFHOMYKL", "Kuwait Finance House"),
+    ("MBBEMYKL", "Maybank"),
+    ("MFBBMYKL", "Alliance Bank"),
+    ("OCBCMYKL", "OCBC Bank"),
+    ("PBBEMYKL", "Public Bank"),
+    ("PHBMMYKL", "Affin Bank"),
+    ("RHBBMYKL", "RHB Bank"),
+    ("RJHIMYKL", "Al Rajhi Bank"),
+    ("SCBLMYKX", "Standard Chartered"),
+    ("UOVBMYKL", "UOB Bank"),
+];
+
+/// How long `fetch_fpx_banks` reuses a fetched list by default.
+pub const DEFAULT_FPX_BANK_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Whether `code` is a payout (SWIFT) bank code. Staging also accepts the test banks.
+pub(crate) fn is_payout_bank_code(code: &str, environment: Option<&Environment>) -> bool {
+    let staging = matches!(environment, Some(Environment::Staging));
+    PAYOUT_BANKS
+        .iter()
+        .chain(if staging { STAGING_TEST_BANKS } else { &[] })
+        .any(|(known, _)| *known == code)
+}
+
+/// Hardcoded FPX banks. If the client environment is Staging, test banks are appended.
+pub(crate) fn known_banks(environment: Option<&Environment>) -> Vec<FpxBank> {
+    let mut table = PRODUCTION_BANKS.to_vec();
+    if matches!(environment, Some(Environment::Staging)) {
+        table.extend_from_slice(STAGING_TEST_BANKS);
+    }
+    table
+        .into_iter()
+        .map(|(code, name)| FpxBank {
+            bank_code: code.into(),
+            bank_name: name.into(),
+            active: true,
+        })
+        .collect()
+}
+
+fn bank_name(code: &str) -> String {
+    PRODUCTION_BANKS
+        .iter()
+        .chain(STAGING_TEST_BANKS)
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| code.to_string())
 }
 
-fn staging_test_banks() -> Vec<FpxBank> {
-    vec![
-        FpxBank { bank_code: "TEST0001".into(), bank_name: "Test 0001".into() },
-        FpxBank { bank_code: "TEST0002".into(), bank_name: "Test 0002".into() },
-        FpxBank { bank_code: "TEST0003".into(), bank_name: "Test 0003".into() },
-        FpxBank { bank_code: "TEST0004".into(), bank_name: "Test 0004".into() },
-        FpxBank { bank_code: "TEST0021".into(), bank_name: "Test 0021".into() },
-        FpxBank { bank_code: "TEST0022".into(), bank_name: "Test 0022".into() },
-        FpxBank { bank_code: "TEST0023".into(), bank_name: "Test 0023".into() },
-    ]
+/// The last live FPX bank list, kept on `BillplzClient`.
+pub(crate) struct FpxBankCache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, Vec<FpxBank>)>>,
+}
+
+impl FpxBankCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// The cached list, if it is younger than the TTL (or at any age when `allow_stale`).
+    fn get(&self, allow_stale: bool) -> Option<Vec<FpxBank>> {
+        let entry = self.entry.lock().unwrap();
+        entry
+            .as_ref()
+            .filter(|(fetched_at, _)| allow_stale || fetched_at.elapsed() < self.ttl)
+            .map(|(_, banks)| banks.clone())
+    }
+
+    fn set(&self, banks: Vec<FpxBank>) {
+        *self.entry.lock().unwrap() = Some((Instant::now(), banks));
+    }
 }
 
 pub struct CreateBankVerificationBuilder<'a> {
@@ -48,6 +140,7 @@ pub struct CreateBankVerificationBuilder<'a> {
     acc_no: String,
     code: String,
     organization: bool,
+    validate_on_send: bool,
 }
 
 impl<'a> CreateBankVerificationBuilder<'a> {
@@ -65,6 +158,7 @@ impl<'a> CreateBankVerificationBuilder<'a> {
             acc_no: acc_no.into(),
             code: code.into(),
             organization: false,
+            validate_on_send: true,
         }
     }
 
@@ -73,7 +167,40 @@ impl<'a> CreateBankVerificationBuilder<'a> {
         self
     }
 
-    pub async fn send(self) -> Result<String, BillplzError> {
+    /// Whether `send` runs `validate` first. Defaults to true.
+    pub fn validate_on_send(mut self, enabled: bool) -> Self {
+        self.validate_on_send = enabled;
+        self
+    }
+
+    /// Checks for a name and ID number, a 6 to 20 digit account number and
+    /// a SWIFT-format bank code.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        errors.require("name", &self.name);
+        errors.require("id_no", &self.id_no);
+        if !validation::is_account_number(&self.acc_no) {
+            errors.push(
+                "acc_no",
+                format!("{:?} must be 6 to 20 digits", self.acc_no),
+            );
+        }
+        if !validation::is_bank_code(&self.code) {
+            errors.push(
+                "code",
+                format!(
+                    "{:?} must be a SWIFT code of 8 or 11 letters and digits",
+                    self.code
+                ),
+            );
+        }
+        errors.finish()
+    }
+
+    pub async fn send(self) -> Result<BankVerificationResponse, BillplzError> {
+        if self.validate_on_send {
+            self.validate()?;
+        }
         let url = format!("{}/api/v3/bank_verification_services", self.client.base_url);
 
         let body = Bank {
@@ -84,51 +211,89 @@ impl<'a> CreateBankVerificationBuilder<'a> {
             organization: self.organization,
         };
 
-        let resp = self
+        let request = self
             .client
             .http
             .post(&url)
             .basic_auth(&self.client.api_key, Option::<&str>::None)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+
+        let resp = self.client.execute(request).await?;
 
-        let text = resp.text().await?;
-        Ok(text)
+        self.client.parse_response(resp).await
     }
 }
 
 impl BillplzClient {
-    /// Returns the hardcoded list of Malaysian FPX banks.
-    /// Staging environment also includes test banks.
+    /// Returns the hardcoded list of Malaysian FPX banks without a network call.
+    /// Staging environment also includes test banks. See `fetch_fpx_banks` for the live list.
     pub fn get_fpx_banks(&self) -> Vec<FpxBank> {
-        let mut banks = production_banks();
-        if matches!(self.environment, Some(Environment::Staging)) {
-            banks.extend(staging_test_banks());
+        known_banks(self.environment.as_ref())
+    }
+
+    /// GET /api/v3/fpx_banks
+    ///
+    /// Returns the live FPX bank list with each bank's `active` flag. Results are
+    /// cached for the client's FPX bank cache TTL. When Billplz cannot be reached,
+    /// the last fetched list (or the hardcoded one) is returned instead.
+    pub async fn fetch_fpx_banks(&self) -> Result<Vec<FpxBank>, BillplzError> {
+        if let Some(banks) = self.fpx_bank_cache.get(false) {
+            return Ok(banks);
         }
-        banks
+
+        match self.request_fpx_banks().await {
+            Ok(banks) => {
+                self.fpx_bank_cache.set(banks.clone());
+                Ok(banks)
+            }
+            Err(e) if e.is_retryable() => Ok(self
+                .fpx_bank_cache
+                .get(true)
+                .unwrap_or_else(|| self.get_fpx_banks())),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn request_fpx_banks(&self) -> Result<Vec<FpxBank>, BillplzError> {
+        let url = format!("{}/api/v3/fpx_banks", self.base_url);
+
+        let request = self
+            .http
+            .get(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        let list: FpxBankList = self.parse_response(resp).await?;
+        Ok(list
+            .banks
+            .into_iter()
+            .map(|bank| FpxBank {
+                bank_name: bank_name(&bank.name),
+                bank_code: bank.name,
+                active: bank.active,
+            })
+            .collect())
     }
 
     /// GET /api/v3/bank_verification_services/{bank_account_number}
-    /// Returns the raw response body as a String.
     pub async fn get_bank_verification(
         &self,
         bank_account_number: &str,
-    ) -> Result<String, BillplzError> {
+    ) -> Result<BankVerificationResponse, BillplzError> {
         let url = format!(
             "{}/api/v3/bank_verification_services/{}",
             self.base_url, bank_account_number
         );
 
-        let resp = self
+        let request = self
             .http
             .get(&url)
-            .basic_auth(&self.api_key, Option::<&str>::None)
-            .send()
-            .await?;
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
 
-        let text = resp.text().await?;
-        Ok(text)
+        self.parse_response(resp).await
     }
 
     /// POST /api/v3/bank_verification_services
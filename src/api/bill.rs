@@ -1,13 +1,25 @@
+use crate::api::paginator::Paginator;
 use crate::client::BillplzClient;
 use crate::error::BillplzError;
-use crate::models::bill::{Bill, BillResponse};
+use crate::idempotency::{IdempotencyField, IdempotencyRecord};
+use crate::models::bank::FpxBankCode;
+use crate::models::bill::{
+    with_auto_submit, AutoSubmit, Bill, BillResponse, BillTransactions, Transaction,
+    BANK_CODE_LABEL,
+};
+use crate::models::money::Money;
+use crate::validation::{self, ValidationError};
 
+/// Smallest bill amount Billplz accepts, in sen.
+const MIN_BILL_AMOUNT: i64 = 100;
+
+#[derive(Clone)]
 pub struct CreateBillBuilder<'a> {
     client: &'a BillplzClient,
     collection_id: String,
     email: String,
     name: String,
-    amount: i64,
+    amount: Money,
     callback_url: String,
     description: String,
     due_at: String,
@@ -18,6 +30,10 @@ pub struct CreateBillBuilder<'a> {
     reference_1: Option<String>,
     reference_2_label: Option<String>,
     reference_2: Option<String>,
+    idempotency_key: Option<String>,
+    idempotency_field: IdempotencyField,
+    auto_submit: Option<AutoSubmit>,
+    validate_on_send: bool,
 }
 
 impl<'a> CreateBillBuilder<'a> {
@@ -27,7 +43,7 @@ impl<'a> CreateBillBuilder<'a> {
         collection_id: impl Into<String>,
         email: impl Into<String>,
         name: impl Into<String>,
-        amount: i64,
+        amount: impl Into<Money>,
         callback_url: impl Into<String>,
         description: impl Into<String>,
         due_at: impl Into<String>,
@@ -37,7 +53,7 @@ impl<'a> CreateBillBuilder<'a> {
             collection_id: collection_id.into(),
             email: email.into(),
             name: name.into(),
-            amount,
+            amount: amount.into(),
             callback_url: callback_url.into(),
             description: description.into(),
             due_at: due_at.into(),
@@ -48,6 +64,10 @@ impl<'a> CreateBillBuilder<'a> {
             reference_1: None,
             reference_2_label: None,
             reference_2: None,
+            idempotency_key: None,
+            idempotency_field: IdempotencyField::default(),
+            auto_submit: None,
+            validate_on_send: true,
         }
     }
 
@@ -86,7 +106,156 @@ impl<'a> CreateBillBuilder<'a> {
         self
     }
 
-    pub async fn send(self) -> Result<BillResponse, BillplzError> {
+    /// Preselects the payer's FPX bank by setting `reference_1_label` to
+    /// `"Bank Code"` and `reference_1` to the code. Overrides any reference 1.
+    /// Use `BillResponse::checkout_url` to build the bypass URL.
+    pub fn preferred_bank(mut self, bank_code: impl Into<FpxBankCode>) -> Self {
+        self.reference_1_label = Some(BANK_CODE_LABEL.to_string());
+        self.reference_1 = Some(bank_code.into().as_str().to_string());
+        self
+    }
+
+    /// Makes the returned bill's `url` skip the Billplz bill page (see
+    /// `BillResponse::checkout_url`). Requires `preferred_bank`; `validate`
+    /// checks the bank against the built-in FPX list for the client's environment.
+    pub fn auto_submit(mut self, mode: AutoSubmit) -> Self {
+        self.auto_submit = Some(mode);
+        self
+    }
+
+    /// Makes `send` safe to repeat: if a bill was already created with this key,
+    /// it is returned from the client's `IdempotencyStore` instead of creating another.
+    /// The key is claimed as pending before posting, so a send that got no answer
+    /// makes later sends fail with `BillplzError::OutcomeUnknown` instead of
+    /// posting again. While the key is claimed, a 429, 5xx or failed
+    /// connection is retried under the client's `RetryPolicy`; a timeout is
+    /// not. The key is also stored on the bill in `reference_2` (see
+    /// `idempotency_field`), overriding any reference set there.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Which reference field carries the idempotency key. Defaults to `Reference2`.
+    pub fn idempotency_field(mut self, field: IdempotencyField) -> Self {
+        self.idempotency_field = field;
+        self
+    }
+
+    /// Whether `send` runs `validate` first. Defaults to true.
+    pub fn validate_on_send(mut self, enabled: bool) -> Self {
+        self.validate_on_send = enabled;
+        self
+    }
+
+    /// Checks the fields Billplz would reject: a valid email or `60`-prefixed
+    /// mobile, an amount of at least 100 sen, a description of at most 200
+    /// characters, references of at most 120 and `due_at` as `YYYY-MM-DD`.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        errors.require("collection_id", &self.collection_id);
+        match &self.mobile {
+            Some(mobile) if !validation::is_mobile(mobile) => errors.push(
+                "mobile",
+                format!("{:?} must be in the format 60xxxxxxxxx", mobile),
+            ),
+            _ => {}
+        }
+        if !self.email.is_empty() {
+            errors.email("email", &self.email);
+        } else if self.mobile.is_none() {
+            errors.push("email", "is required when no mobile is given");
+        }
+        errors.require("name", &self.name);
+        errors.max_chars("name", &self.name, 255);
+        if self.amount.cents() < MIN_BILL_AMOUNT {
+            errors.push(
+                "amount",
+                format!(
+                    "must be at least {} sen, got {}",
+                    MIN_BILL_AMOUNT,
+                    self.amount.cents()
+                ),
+            );
+        }
+        errors.require("callback_url", &self.callback_url);
+        errors.require("description", &self.description);
+        errors.max_chars("description", &self.description, 200);
+        if !self.due_at.is_empty() && !validation::is_date(&self.due_at) {
+            errors.push(
+                "due_at",
+                format!("{:?} must be a date as YYYY-MM-DD", self.due_at),
+            );
+        }
+        let references = [
+            ("reference_1_label", &self.reference_1_label, 20),
+            ("reference_1", &self.reference_1, 120),
+            ("reference_2_label", &self.reference_2_label, 20),
+            ("reference_2", &self.reference_2, 120),
+        ];
+        for (field, value, max) in references {
+            if let Some(value) = value {
+                errors.max_chars(field, value, max);
+            }
+        }
+        if self.auto_submit.is_some() {
+            match (&self.reference_1_label, &self.reference_1) {
+                (Some(label), Some(code)) if label == BANK_CODE_LABEL => {
+                    // Without an environment (a custom base URL) there is no list to check.
+                    if let Some(environment) = &self.client.environment {
+                        let banks = crate::api::bank::known_banks(Some(environment));
+                        if !banks.iter().any(|b| b.bank_code == *code) {
+                            errors.push("reference_1", format!("unknown FPX bank code {:?}", code));
+                        }
+                    }
+                }
+                _ => errors.push("auto_submit", "requires preferred_bank"),
+            }
+        }
+        errors.finish()
+    }
+
+    pub async fn send(mut self) -> Result<BillResponse, BillplzError> {
+        let Some(key) = self.idempotency_key.clone() else {
+            if self.validate_on_send {
+                self.validate()?;
+            }
+            return self.post(false).await;
+        };
+
+        match self.idempotency_field {
+            IdempotencyField::Reference1 => self.reference_1 = Some(key.clone()),
+            IdempotencyField::Reference2 => self.reference_2 = Some(key.clone()),
+        }
+        if self.validate_on_send {
+            self.validate()?;
+        }
+
+        let client = self.client;
+        let _guard = client.idempotency_locks.lock(&key).await;
+        let store = &client.idempotency_store;
+        match store.begin(&key)? {
+            Some(IdempotencyRecord::Completed(bill)) => return Ok(*bill),
+            Some(IdempotencyRecord::Pending) => return Err(BillplzError::OutcomeUnknown { key }),
+            None => {}
+        }
+        // The key is held as pending, so transient failures can be retried.
+        match self.post(true).await {
+            Ok(bill) => {
+                store.complete(&key, &bill)?;
+                Ok(bill)
+            }
+            // Billplz answered with a 4xx, so no bill was created.
+            Err(e) if e.status().is_some_and(|status| status < 500) => {
+                store.release(&key)?;
+                Err(e)
+            }
+            // No answer, a 5xx or an unreadable response: the bill may exist.
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn post(self, claimed: bool) -> Result<BillResponse, BillplzError> {
         let url = format!("{}/api/v3/bills", self.client.base_url);
 
         let body = Bill {
@@ -106,40 +275,138 @@ impl<'a> CreateBillBuilder<'a> {
             reference_2: self.reference_2,
         };
 
-        let resp = self
+        let request = self
             .client
             .http
             .post(&url)
             .basic_auth(&self.client.api_key, Option::<&str>::None)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+
+        let resp = if claimed {
+            self.client.execute_claimed(request).await?
+        } else {
+            self.client.execute(request).await?
+        };
+
+        let mut bill: BillResponse = self.client.parse_response(resp).await?;
+        if let (Some(mode), Some(url)) = (self.auto_submit, &bill.url) {
+            bill.url = Some(with_auto_submit(url, mode)?);
+        }
+        Ok(bill)
+    }
+}
+
+#[derive(Clone)]
+pub struct GetBillTransactionsBuilder<'a> {
+    client: &'a BillplzClient,
+    bill_id: String,
+    page: Option<u32>,
+    status: Option<String>,
+}
+
+impl<'a> GetBillTransactionsBuilder<'a> {
+    pub(crate) fn new(client: &'a BillplzClient, bill_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            bill_id: bill_id.into(),
+            page: None,
+            status: None,
+        }
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Filter by transaction status: `pending`, `completed` or `failed`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    pub async fn send(self) -> Result<BillTransactions, BillplzError> {
+        let url = format!(
+            "{}/api/v3/bills/{}/transactions",
+            self.client.base_url, self.bill_id
+        );
+
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(status) = self.status {
+            query.push(("status", status));
+        }
+
+        let request = self
+            .client
+            .http
+            .get(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .query(&query);
+
+        let resp = self.client.execute(request).await?;
 
         self.client.parse_response(resp).await
     }
+
+    /// Streams every transaction from `page` (default 1) onwards, fetching pages lazily.
+    pub fn paginate(self) -> Paginator<'a, Transaction> {
+        let start = self.page.unwrap_or(1);
+        Paginator::new(start, move |page| {
+            let builder = self.clone().page(page);
+            async move { builder.send().await.map(|list| list.transactions) }
+        })
+    }
 }
 
 impl BillplzClient {
     pub async fn get_bill(&self, bill_id: impl Into<String>) -> Result<BillResponse, BillplzError> {
         let url = format!("{}/api/v3/bills/{}", self.base_url, bill_id.into());
 
-        let resp = self
+        let request = self
             .http
             .get(&url)
-            .basic_auth(&self.api_key, Option::<&str>::None)
-            .send()
-            .await?;
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
 
         self.parse_response(resp).await
     }
 
+    /// DELETE /api/v3/bills/{id}
+    /// Only unpaid bills can be deleted.
+    pub async fn delete_bill(&self, bill_id: impl Into<String>) -> Result<(), BillplzError> {
+        let url = format!("{}/api/v3/bills/{}", self.base_url, bill_id.into());
+
+        let request = self
+            .http
+            .delete(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response::<serde::de::IgnoredAny>(resp).await?;
+        Ok(())
+    }
+
+    /// GET /api/v3/bills/{id}/transactions
+    /// Returns a builder with optional `page` and `status` filters.
+    pub fn get_bill_transactions(
+        &self,
+        bill_id: impl Into<String>,
+    ) -> GetBillTransactionsBuilder<'_> {
+        GetBillTransactionsBuilder::new(self, bill_id)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn create_bill(
         &self,
         collection_id: impl Into<String>,
         email: impl Into<String>,
         name: impl Into<String>,
-        amount: i64,
+        amount: impl Into<Money>,
         callback_url: impl Into<String>,
         description: impl Into<String>,
         due_at: impl Into<String>,
@@ -0,0 +1,203 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, Stream, StreamExt};
+
+use crate::api::bank::is_payout_bank_code;
+use crate::api::bill::CreateBillBuilder;
+use crate::api::payout::CreatePayoutBuilder;
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::bank::BankVerificationStatus;
+use crate::models::bill::BillResponse;
+use crate::models::payout::PayoutResponse;
+use crate::validation::ValidationError;
+
+/// Result of one item in a bulk run, tagged with its position in the input.
+#[derive(Debug)]
+pub struct BulkOutcome<T> {
+    pub index: usize,
+    pub result: Result<T, BillplzError>,
+}
+
+/// Settings for `create_payouts_bulk`.
+#[derive(Debug, Clone)]
+pub struct BulkPayoutOptions {
+    /// Maximum number of payouts in flight. Defaults to 4.
+    pub concurrency: usize,
+    /// Look up each account with `get_bank_verification` and skip any that is
+    /// not `verified`, reporting `BillplzError::UnverifiedAccount`.
+    pub require_verified: bool,
+}
+
+impl Default for BulkPayoutOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            require_verified: false,
+        }
+    }
+}
+
+impl BulkPayoutOptions {
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn require_verified(mut self, require_verified: bool) -> Self {
+        self.require_verified = require_verified;
+        self
+    }
+}
+
+/// Shared pause so that one 429 holds back every worker, not just the one that hit it.
+#[derive(Clone, Default)]
+struct RateLimitGate {
+    until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl RateLimitGate {
+    async fn wait(&self) {
+        let until = *self.until.lock().unwrap();
+        if let Some(until) = until {
+            let now = Instant::now();
+            if until > now {
+                tokio::time::sleep(until - now).await;
+            }
+        }
+    }
+
+    fn pause(&self, delay: Duration) {
+        let resume_at = Instant::now() + delay;
+        let mut until = self.until.lock().unwrap();
+        if until.is_none_or(|u| u < resume_at) {
+            *until = Some(resume_at);
+        }
+    }
+}
+
+impl BillplzClient {
+    /// Sends `items` with at most `concurrency` requests in flight.
+    ///
+    /// A 429 pauses all workers for the `Retry-After` delay (or the retry
+    /// policy's backoff) and resends the item, up to `max_attempts` times.
+    /// Other errors are reported, not retried.
+    pub(crate) fn run_bulk<'a, I, B, T, F, Fut>(
+        &'a self,
+        items: I,
+        concurrency: usize,
+        send: F,
+    ) -> impl Stream<Item = BulkOutcome<T>> + Send + 'a
+    where
+        I: IntoIterator<Item = B>,
+        I::IntoIter: Send + 'a,
+        B: Clone + Send + 'a,
+        T: Send + 'a,
+        F: Fn(B) -> Fut + Clone + Send + 'a,
+        Fut: Future<Output = Result<T, BillplzError>> + Send + 'a,
+    {
+        let gate = RateLimitGate::default();
+        // Items are pulled from the iterator only as workers free up.
+        stream::iter(items.into_iter().enumerate())
+            .map(move |(index, item)| {
+                let gate = gate.clone();
+                let send = send.clone();
+                async move {
+                    let mut attempt = 1;
+                    let result = loop {
+                        gate.wait().await;
+                        match send(item.clone()).await {
+                            Err(BillplzError::RateLimited { retry_after, .. })
+                                if attempt < self.retry_policy.max_attempts =>
+                            {
+                                gate.pause(self.retry_policy.delay(attempt, retry_after));
+                                attempt += 1;
+                            }
+                            result => break result,
+                        }
+                    };
+                    BulkOutcome { index, result }
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
+
+    /// Creates many bills with bounded parallelism.
+    ///
+    /// Outcomes are yielded as they complete, not in input order; use
+    /// `BulkOutcome::index` to match them up. Set `idempotency_key` on each
+    /// builder to make a re-run skip bills that were already created.
+    pub fn create_bills_bulk<'a, I>(
+        &'a self,
+        bills: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = BulkOutcome<BillResponse>> + Send + 'a
+    where
+        I: IntoIterator<Item = CreateBillBuilder<'a>>,
+        I::IntoIter: Send + 'a,
+    {
+        self.run_bulk(bills, concurrency, |bill: CreateBillBuilder<'a>| bill.send())
+    }
+
+    /// Creates many payouts with bounded parallelism after local validation.
+    ///
+    /// Each payout is first checked with `CreatePayoutBuilder::validate`, and
+    /// its bank code must be one of the known SWIFT payout banks (plus the
+    /// test banks on Staging). A bad or unknown bank code, a malformed account
+    /// number or a non-positive total is reported as
+    /// `BillplzError::InvalidRequest` without calling the API. With `require_verified`, accounts whose bank
+    /// verification is missing or not `verified` are skipped. Outcomes are
+    /// yielded as they complete; use `BulkOutcome::index` to match them up.
+    pub fn create_payouts_bulk<'a, I>(
+        &'a self,
+        payouts: I,
+        options: BulkPayoutOptions,
+    ) -> impl Stream<Item = BulkOutcome<PayoutResponse>> + Send + 'a
+    where
+        I: IntoIterator<Item = CreatePayoutBuilder<'a>>,
+        I::IntoIter: Send + 'a,
+    {
+        let require_verified = options.require_verified;
+        self.run_bulk(
+            payouts,
+            options.concurrency,
+            move |payout: CreatePayoutBuilder<'a>| async move {
+                payout.validate()?;
+                self.check_payout_bank(payout.bank_code())?;
+                if require_verified {
+                    self.check_verified(payout.bank_account_number()).await?;
+                }
+                payout.send().await
+            },
+        )
+    }
+
+    fn check_payout_bank(&self, code: &str) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if !is_payout_bank_code(code, self.environment.as_ref()) {
+            errors.push(
+                "bank_code",
+                format!("{:?} is not a known payout bank", code),
+            );
+        }
+        errors.finish()
+    }
+
+    async fn check_verified(&self, account_number: &str) -> Result<(), BillplzError> {
+        let status = match self.get_bank_verification(account_number).await {
+            Ok(verification) => verification.status,
+            Err(e) if e.is_not_found() => BankVerificationStatus::Unknown("not found".into()),
+            Err(e) => return Err(e),
+        };
+        if status == BankVerificationStatus::Verified {
+            Ok(())
+        } else {
+            Err(BillplzError::UnverifiedAccount {
+                account_number: account_number.to_string(),
+                status: status.as_str().to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::card::{Card, CardResponse, ChargeRequest, ChargeResponse, DeleteCardRequest};
+
+pub struct CreateCardBuilder<'a> {
+    client: &'a BillplzClient,
+    name: String,
+    email: String,
+    phone: String,
+    card_number: String,
+    cvv: String,
+    expiry: String,
+}
+
+impl<'a> CreateCardBuilder<'a> {
+    pub(crate) fn new(
+        client: &'a BillplzClient,
+        name: impl Into<String>,
+        email: impl Into<String>,
+        phone: impl Into<String>,
+        card_number: impl Into<String>,
+        cvv: impl Into<String>,
+        expiry: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            name: name.into(),
+            email: email.into(),
+            phone: phone.into(),
+            card_number: card_number.into(),
+            cvv: cvv.into(),
+            expiry: expiry.into(),
+        }
+    }
+
+    pub async fn send(self) -> Result<CardResponse, BillplzError> {
+        let url = format!("{}/api/v4/cards", self.client.base_url);
+
+        let body = Card {
+            name: self.name,
+            email: self.email,
+            phone: self.phone,
+            card_number: self.card_number,
+            cvv: self.cvv,
+            expiry: self.expiry,
+        };
+
+        let request = self
+            .client
+            .http
+            .post(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+}
+
+impl BillplzClient {
+    /// POST /api/v4/cards
+    /// Tokenizes a card. Keep the returned `id` and `token` to charge it later.
+    /// `expiry` is `MM/YY`.
+    pub fn create_card(
+        &self,
+        name: impl Into<String>,
+        email: impl Into<String>,
+        phone: impl Into<String>,
+        card_number: impl Into<String>,
+        cvv: impl Into<String>,
+        expiry: impl Into<String>,
+    ) -> CreateCardBuilder<'_> {
+        CreateCardBuilder::new(self, name, email, phone, card_number, cvv, expiry)
+    }
+
+    /// DELETE /api/v4/cards/{id}
+    pub async fn delete_card(
+        &self,
+        card_id: &str,
+        token: &str,
+    ) -> Result<CardResponse, BillplzError> {
+        let url = format!("{}/api/v4/cards/{}", self.base_url, card_id);
+
+        let body = DeleteCardRequest {
+            token: token.to_string(),
+        };
+
+        let request = self
+            .http
+            .delete(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response(resp).await
+    }
+
+    /// POST /api/v4/bills/{id}/charge
+    /// Charges an existing bill to a tokenized card.
+    pub async fn charge_bill_with_token(
+        &self,
+        bill_id: &str,
+        card_id: &str,
+        token: &str,
+    ) -> Result<ChargeResponse, BillplzError> {
+        let url = format!("{}/api/v4/bills/{}/charge", self.base_url, bill_id);
+
+        let body = ChargeRequest {
+            card_id: card_id.to_string(),
+            token: token.to_string(),
+        };
+
+        let request = self
+            .http
+            .post(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response(resp).await
+    }
+}
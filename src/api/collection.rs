@@ -1,12 +1,16 @@
+use crate::api::paginator::Paginator;
 use crate::client::BillplzClient;
 use crate::error::BillplzError;
-use crate::models::collection::{Collection, CollectionResponse, SplitPayment};
+use crate::models::collection::{Collection, CollectionList, CollectionResponse, SplitPayment};
+use crate::models::money::Money;
+use crate::validation::ValidationError;
 
 pub struct CreateCollectionBuilder<'a> {
     client: &'a BillplzClient,
     title: String,
     split_header: Option<bool>,
     split_payments: Vec<SplitPayment>,
+    validate_on_send: bool,
 }
 
 impl<'a> CreateCollectionBuilder<'a> {
@@ -16,6 +20,7 @@ impl<'a> CreateCollectionBuilder<'a> {
             title: title.into(),
             split_header: None,
             split_payments: Vec::new(),
+            validate_on_send: true,
         }
     }
 
@@ -37,12 +42,12 @@ impl<'a> CreateCollectionBuilder<'a> {
     pub fn split_payment_with_fixed_cut(
         mut self,
         email: impl Into<String>,
-        fixed_cut: i64,
+        fixed_cut: impl Into<Money>,
         stack_order: i32,
     ) -> Self {
         self.split_payments.push(SplitPayment {
             email: email.into(),
-            fixed_cut: Some(fixed_cut),
+            fixed_cut: Some(fixed_cut.into()),
             variable_cut: None,
             stack_order,
         });
@@ -64,7 +69,40 @@ impl<'a> CreateCollectionBuilder<'a> {
         self
     }
 
+    /// Whether `send` runs `validate` first. Defaults to true.
+    pub fn validate_on_send(mut self, enabled: bool) -> Self {
+        self.validate_on_send = enabled;
+        self
+    }
+
+    /// Checks the title (required, at most 50 characters) and each split
+    /// payment's email and variable cut (a percentage from 0 to 100).
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        errors.require("title", &self.title);
+        errors.max_chars("title", &self.title, 50);
+        for (i, split) in self.split_payments.iter().enumerate() {
+            errors.email(&format!("split_payments[{}].email", i), &split.email);
+            if let Some(cut) = &split.variable_cut {
+                let valid = cut
+                    .trim()
+                    .parse::<f64>()
+                    .is_ok_and(|cut| (0.0..=100.0).contains(&cut));
+                if !valid {
+                    errors.push(
+                        format!("split_payments[{}].variable_cut", i),
+                        format!("{:?} must be a percentage from 0 to 100", cut),
+                    );
+                }
+            }
+        }
+        errors.finish()
+    }
+
     pub async fn send(self) -> Result<CollectionResponse, BillplzError> {
+        if self.validate_on_send {
+            self.validate()?;
+        }
         let url = format!("{}/api/v4/collections", self.client.base_url);
 
         let body = Collection {
@@ -77,19 +115,79 @@ impl<'a> CreateCollectionBuilder<'a> {
             },
         };
 
-        let resp = self
+        let request = self
             .client
             .http
             .post(&url)
             .basic_auth(&self.client.api_key, Option::<&str>::None)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+
+        let resp = self.client.execute(request).await?;
 
         self.client.parse_response(resp).await
     }
 }
 
+#[derive(Clone)]
+pub struct ListCollectionsBuilder<'a> {
+    client: &'a BillplzClient,
+    page: Option<u32>,
+    status: Option<String>,
+}
+
+impl<'a> ListCollectionsBuilder<'a> {
+    pub(crate) fn new(client: &'a BillplzClient) -> Self {
+        Self {
+            client,
+            page: None,
+            status: None,
+        }
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Filter by collection status: `active` or `inactive`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    pub async fn send(self) -> Result<CollectionList, BillplzError> {
+        let url = format!("{}/api/v4/collections", self.client.base_url);
+
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(status) = self.status {
+            query.push(("status", status));
+        }
+
+        let request = self
+            .client
+            .http
+            .get(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .query(&query);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+
+    /// Streams every collection from `page` (default 1) onwards, fetching pages lazily.
+    pub fn paginate(self) -> Paginator<'a, CollectionResponse> {
+        let start = self.page.unwrap_or(1);
+        Paginator::new(start, move |page| {
+            let builder = self.clone().page(page);
+            async move { builder.send().await.map(|list| list.collections) }
+        })
+    }
+}
+
 impl BillplzClient {
     pub async fn get_collection(
         &self,
@@ -97,12 +195,12 @@ impl BillplzClient {
     ) -> Result<CollectionResponse, BillplzError> {
         let url = format!("{}/api/v4/collections/{}", self.base_url, collection_id);
 
-        let resp = self
+        let request = self
             .http
             .get(&url)
-            .basic_auth(&self.api_key, Option::<&str>::None)
-            .send()
-            .await?;
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
 
         self.parse_response(resp).await
     }
@@ -110,4 +208,43 @@ impl BillplzClient {
     pub fn create_collection(&self, title: impl Into<String>) -> CreateCollectionBuilder<'_> {
         CreateCollectionBuilder::new(self, title)
     }
+
+    /// GET /api/v4/collections
+    /// Returns a builder with optional `page` and `status` filters.
+    pub fn list_collections(&self) -> ListCollectionsBuilder<'_> {
+        ListCollectionsBuilder::new(self)
+    }
+
+    /// POST /api/v3/collections/{id}/activate
+    pub async fn activate_collection(&self, collection_id: &str) -> Result<(), BillplzError> {
+        self.set_collection_state("collections", collection_id, "activate").await
+    }
+
+    /// POST /api/v3/collections/{id}/deactivate
+    pub async fn deactivate_collection(&self, collection_id: &str) -> Result<(), BillplzError> {
+        self.set_collection_state("collections", collection_id, "deactivate").await
+    }
+
+    /// POST /api/v3/{resource}/{id}/{action}, shared by collections and open collections.
+    pub(crate) async fn set_collection_state(
+        &self,
+        resource: &str,
+        collection_id: &str,
+        action: &str,
+    ) -> Result<(), BillplzError> {
+        let url = format!(
+            "{}/api/v3/{}/{}/{}",
+            self.base_url, resource, collection_id, action
+        );
+
+        let request = self
+            .http
+            .post(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response::<serde::de::IgnoredAny>(resp).await?;
+        Ok(())
+    }
 }
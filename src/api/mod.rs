@@ -1,5 +1,12 @@
 pub mod bank;
 pub mod bill;
+pub mod bulk;
+pub mod card;
 pub mod collection;
+pub mod open_collection;
+pub mod paginator;
+pub mod payment_method;
+pub mod payment_order;
 pub mod payout;
 pub mod payout_collection;
+pub mod wait;
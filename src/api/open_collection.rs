@@ -0,0 +1,375 @@
+use crate::api::paginator::Paginator;
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::collection::SplitPayment;
+use crate::models::money::Money;
+use crate::models::open_collection::{OpenCollection, OpenCollectionList, OpenCollectionResponse};
+
+struct PhotoUpload {
+    bytes: Vec<u8>,
+    file_name: String,
+}
+
+pub struct CreateOpenCollectionBuilder<'a> {
+    client: &'a BillplzClient,
+    title: String,
+    description: String,
+    amount: Option<Money>,
+    fixed_amount: Option<bool>,
+    fixed_quantity: Option<bool>,
+    payment_button: Option<String>,
+    reference_1_label: Option<String>,
+    reference_2_label: Option<String>,
+    email_link: Option<String>,
+    tax: Option<i32>,
+    redirect_uri: Option<String>,
+    photo: Option<PhotoUpload>,
+    split_header: Option<bool>,
+    split_payments: Vec<SplitPayment>,
+}
+
+impl<'a> CreateOpenCollectionBuilder<'a> {
+    pub(crate) fn new(
+        client: &'a BillplzClient,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            title: title.into(),
+            description: description.into(),
+            amount: None,
+            fixed_amount: None,
+            fixed_quantity: None,
+            payment_button: None,
+            reference_1_label: None,
+            reference_2_label: None,
+            email_link: None,
+            tax: None,
+            redirect_uri: None,
+            photo: None,
+            split_header: None,
+            split_payments: Vec::new(),
+        }
+    }
+
+    /// Fixed amount charged per payment. Required unless `fixed_amount(false)` is set.
+    pub fn amount(mut self, amount: impl Into<Money>) -> Self {
+        self.amount = Some(amount.into());
+        self
+    }
+
+    /// Set to `false` to let payers enter their own amount.
+    pub fn fixed_amount(mut self, fixed_amount: bool) -> Self {
+        self.fixed_amount = Some(fixed_amount);
+        self
+    }
+
+    /// Set to `false` to let payers choose a quantity.
+    pub fn fixed_quantity(mut self, fixed_quantity: bool) -> Self {
+        self.fixed_quantity = Some(fixed_quantity);
+        self
+    }
+
+    /// Button label on the payment form: `pay` or `buy`.
+    pub fn payment_button(mut self, payment_button: impl Into<String>) -> Self {
+        self.payment_button = Some(payment_button.into());
+        self
+    }
+
+    pub fn reference_1_label(mut self, label: impl Into<String>) -> Self {
+        self.reference_1_label = Some(label.into());
+        self
+    }
+
+    pub fn reference_2_label(mut self, label: impl Into<String>) -> Self {
+        self.reference_2_label = Some(label.into());
+        self
+    }
+
+    pub fn email_link(mut self, email_link: impl Into<String>) -> Self {
+        self.email_link = Some(email_link.into());
+        self
+    }
+
+    /// Tax rate in percent.
+    pub fn tax(mut self, tax: i32) -> Self {
+        self.tax = Some(tax);
+        self
+    }
+
+    pub fn redirect_uri(mut self, redirect_uri: impl Into<String>) -> Self {
+        self.redirect_uri = Some(redirect_uri.into());
+        self
+    }
+
+    /// Image shown on the payment form. Sending a photo switches the request to multipart.
+    pub fn photo(mut self, bytes: impl Into<Vec<u8>>, file_name: impl Into<String>) -> Self {
+        self.photo = Some(PhotoUpload {
+            bytes: bytes.into(),
+            file_name: file_name.into(),
+        });
+        self
+    }
+
+    pub fn split_header(mut self, split_header: bool) -> Self {
+        self.split_header = Some(split_header);
+        self
+    }
+
+    pub fn split_payment(mut self, email: impl Into<String>, stack_order: i32) -> Self {
+        self.split_payments.push(SplitPayment {
+            email: email.into(),
+            fixed_cut: None,
+            variable_cut: None,
+            stack_order,
+        });
+        self
+    }
+
+    pub fn split_payment_with_fixed_cut(
+        mut self,
+        email: impl Into<String>,
+        fixed_cut: impl Into<Money>,
+        stack_order: i32,
+    ) -> Self {
+        self.split_payments.push(SplitPayment {
+            email: email.into(),
+            fixed_cut: Some(fixed_cut.into()),
+            variable_cut: None,
+            stack_order,
+        });
+        self
+    }
+
+    pub fn split_payment_with_variable_cut(
+        mut self,
+        email: impl Into<String>,
+        variable_cut: impl Into<String>,
+        stack_order: i32,
+    ) -> Self {
+        self.split_payments.push(SplitPayment {
+            email: email.into(),
+            fixed_cut: None,
+            variable_cut: Some(variable_cut.into()),
+            stack_order,
+        });
+        self
+    }
+
+    pub async fn send(self) -> Result<OpenCollectionResponse, BillplzError> {
+        let url = format!("{}/api/v4/open_collections", self.client.base_url);
+
+        let body = OpenCollection {
+            title: self.title,
+            description: self.description,
+            amount: self.amount,
+            fixed_amount: self.fixed_amount,
+            fixed_quantity: self.fixed_quantity,
+            payment_button: self.payment_button,
+            reference_1_label: self.reference_1_label,
+            reference_2_label: self.reference_2_label,
+            email_link: self.email_link,
+            tax: self.tax,
+            redirect_uri: self.redirect_uri,
+            split_header: self.split_header,
+            split_payments: if self.split_payments.is_empty() {
+                None
+            } else {
+                Some(self.split_payments)
+            },
+        };
+
+        let request = self
+            .client
+            .http
+            .post(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None);
+
+        let request = match self.photo {
+            None => request.json(&body),
+            Some(photo) => {
+                let (content_type, multipart) = multipart_body(form_fields(&body)?, photo);
+                request
+                    .header(reqwest::header::CONTENT_TYPE, content_type)
+                    .body(multipart)
+            }
+        };
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+}
+
+/// Flattens the JSON body into form fields, with `split_payments[][field]` for the array.
+fn form_fields(body: &OpenCollection) -> Result<Vec<(String, String)>, BillplzError> {
+    fn scalar(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    let mut fields = Vec::new();
+    if let serde_json::Value::Object(map) = serde_json::to_value(body)? {
+        for (key, value) in map {
+            match value {
+                serde_json::Value::Array(items) => {
+                    for item in items {
+                        if let serde_json::Value::Object(item) = item {
+                            for (field, v) in item.iter().filter(|(_, v)| !v.is_null()) {
+                                fields.push((format!("{}[][{}]", key, field), scalar(v)));
+                            }
+                        }
+                    }
+                }
+                other => fields.push((key, scalar(&other))),
+            }
+        }
+    }
+    Ok(fields)
+}
+
+/// Encodes text fields plus the photo as `multipart/form-data`.
+/// Built in memory so the body can be replayed by retries and custom transports.
+fn multipart_body(fields: Vec<(String, String)>, photo: PhotoUpload) -> (String, Vec<u8>) {
+    let boundary = format!("billplz-{:016x}{:016x}", fastrand::u64(..), fastrand::u64(..));
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, name, value
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"photo\"; filename=\"{}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n",
+            boundary,
+            photo.file_name.replace('"', "")
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(&photo.bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+#[derive(Clone)]
+pub struct ListOpenCollectionsBuilder<'a> {
+    client: &'a BillplzClient,
+    page: Option<u32>,
+    status: Option<String>,
+}
+
+impl<'a> ListOpenCollectionsBuilder<'a> {
+    pub(crate) fn new(client: &'a BillplzClient) -> Self {
+        Self {
+            client,
+            page: None,
+            status: None,
+        }
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Filter by open collection status: `active` or `inactive`.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    pub async fn send(self) -> Result<OpenCollectionList, BillplzError> {
+        let url = format!("{}/api/v4/open_collections", self.client.base_url);
+
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(status) = self.status {
+            query.push(("status", status));
+        }
+
+        let request = self
+            .client
+            .http
+            .get(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .query(&query);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+
+    /// Streams every open collection from `page` (default 1) onwards, fetching pages lazily.
+    pub fn paginate(self) -> Paginator<'a, OpenCollectionResponse> {
+        let start = self.page.unwrap_or(1);
+        Paginator::new(start, move |page| {
+            let builder = self.clone().page(page);
+            async move { builder.send().await.map(|list| list.open_collections) }
+        })
+    }
+}
+
+impl BillplzClient {
+    /// GET /api/v4/open_collections/{id}
+    pub async fn get_open_collection(
+        &self,
+        open_collection_id: &str,
+    ) -> Result<OpenCollectionResponse, BillplzError> {
+        let url = format!(
+            "{}/api/v4/open_collections/{}",
+            self.base_url, open_collection_id
+        );
+
+        let request = self
+            .http
+            .get(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response(resp).await
+    }
+
+    /// POST /api/v4/open_collections
+    /// Returns a builder for creating a payment form.
+    pub fn create_open_collection(
+        &self,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateOpenCollectionBuilder<'_> {
+        CreateOpenCollectionBuilder::new(self, title, description)
+    }
+
+    /// GET /api/v4/open_collections
+    /// Returns a builder with optional `page` and `status` filters.
+    pub fn list_open_collections(&self) -> ListOpenCollectionsBuilder<'_> {
+        ListOpenCollectionsBuilder::new(self)
+    }
+
+    /// POST /api/v3/open_collections/{id}/activate
+    pub async fn activate_open_collection(
+        &self,
+        open_collection_id: &str,
+    ) -> Result<(), BillplzError> {
+        self.set_collection_state("open_collections", open_collection_id, "activate").await
+    }
+
+    /// POST /api/v3/open_collections/{id}/deactivate
+    pub async fn deactivate_open_collection(
+        &self,
+        open_collection_id: &str,
+    ) -> Result<(), BillplzError> {
+        self.set_collection_state("open_collections", open_collection_id, "deactivate").await
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{Stream, TryStreamExt};
+
+use crate::error::BillplzError;
+
+type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<Vec<T>, BillplzError>> + Send + 'a>>;
+type FetchPage<'a, T> = Box<dyn FnMut(u32) -> PageFuture<'a, T> + Send + 'a>;
+
+/// Lazily walks a page-based list endpoint, yielding one item at a time.
+///
+/// Pages are requested only as the stream is polled, starting at the builder's
+/// `page` (or 1). The stream ends at the first empty page, or after the first
+/// error, which is yielded as the final item.
+pub struct Paginator<'a, T> {
+    fetch: FetchPage<'a, T>,
+    next_page: u32,
+    pages_left: Option<u32>,
+    buffer: VecDeque<T>,
+    in_flight: Option<PageFuture<'a, T>>,
+    done: bool,
+}
+
+// No field is ever pinned in place; the in-flight future is already boxed.
+impl<T> Unpin for Paginator<'_, T> {}
+
+impl<'a, T: Send + 'a> Paginator<'a, T> {
+    pub(crate) fn new<F, Fut>(start_page: u32, mut fetch: F) -> Self
+    where
+        F: FnMut(u32) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Vec<T>, BillplzError>> + Send + 'a,
+    {
+        Self {
+            fetch: Box::new(move |page| Box::pin(fetch(page))),
+            next_page: start_page.max(1),
+            pages_left: None,
+            buffer: VecDeque::new(),
+            in_flight: None,
+            done: false,
+        }
+    }
+
+    /// Stops after fetching at most `pages` pages.
+    pub fn take_pages(mut self, pages: u32) -> Self {
+        self.pages_left = Some(pages);
+        self
+    }
+
+    /// Drains every remaining page into a `Vec`, failing on the first error.
+    pub async fn collect_all(self) -> Result<Vec<T>, BillplzError> {
+        self.try_collect().await
+    }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = Result<T, BillplzError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let page = match &mut this.in_flight {
+                Some(page) => page,
+                None => {
+                    if this.pages_left == Some(0) {
+                        this.done = true;
+                        continue;
+                    }
+                    if let Some(left) = &mut this.pages_left {
+                        *left -= 1;
+                    }
+                    let page = (this.fetch)(this.next_page);
+                    this.next_page += 1;
+                    this.in_flight.insert(page)
+                }
+            };
+
+            let result = match page.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(result) => result,
+            };
+            this.in_flight = None;
+            match result {
+                Ok(items) if items.is_empty() => this.done = true,
+                Ok(items) => this.buffer.extend(items),
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::payment_method::{
+    PaymentGateway, PaymentGatewayList, PaymentMethod, PaymentMethodCode, PaymentMethodCodes,
+    PaymentMethodList,
+};
+
+impl BillplzClient {
+    /// GET /api/v3/collections/{id}/payment_methods
+    pub async fn get_payment_methods(
+        &self,
+        collection_id: &str,
+    ) -> Result<Vec<PaymentMethod>, BillplzError> {
+        let url = format!(
+            "{}/api/v3/collections/{}/payment_methods",
+            self.base_url, collection_id
+        );
+
+        let request = self
+            .http
+            .get(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        let list: PaymentMethodList = self.parse_response(resp).await?;
+        Ok(list.payment_methods)
+    }
+
+    /// PUT /api/v3/collections/{id}/payment_methods
+    /// Enables exactly the given payment method codes on the collection.
+    pub async fn update_payment_methods<I, S>(
+        &self,
+        collection_id: &str,
+        codes: I,
+    ) -> Result<Vec<PaymentMethod>, BillplzError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let url = format!(
+            "{}/api/v3/collections/{}/payment_methods",
+            self.base_url, collection_id
+        );
+
+        let body = PaymentMethodCodes {
+            payment_methods: codes
+                .into_iter()
+                .map(|code| PaymentMethodCode { code: code.into() })
+                .collect(),
+        };
+
+        let request = self
+            .http
+            .put(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.execute(request).await?;
+
+        let list: PaymentMethodList = self.parse_response(resp).await?;
+        Ok(list.payment_methods)
+    }
+
+    /// GET /api/v4/payment_gateways
+    /// Lists the banks and channels currently available to the account.
+    pub async fn get_payment_gateways(&self) -> Result<Vec<PaymentGateway>, BillplzError> {
+        let url = format!("{}/api/v4/payment_gateways", self.base_url);
+
+        let request = self
+            .http
+            .get(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
+
+        let list: PaymentGatewayList = self.parse_response(resp).await?;
+        Ok(list.payment_gateways)
+    }
+}
@@ -0,0 +1,246 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::money::Money;
+use crate::models::payment_order::{
+    PaymentOrder, PaymentOrderCollection, PaymentOrderCollectionResponse, PaymentOrderLimit,
+    PaymentOrderResponse,
+};
+
+/// Current Unix time in seconds, sent as `epoch` and included in every checksum.
+fn epoch() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+pub struct CreatePaymentOrderCollectionBuilder<'a> {
+    client: &'a BillplzClient,
+    title: String,
+    callback_url: Option<String>,
+}
+
+impl<'a> CreatePaymentOrderCollectionBuilder<'a> {
+    pub(crate) fn new(client: &'a BillplzClient, title: impl Into<String>) -> Self {
+        Self {
+            client,
+            title: title.into(),
+            callback_url: None,
+        }
+    }
+
+    /// URL notified when payment orders in the collection change status.
+    pub fn callback_url(mut self, callback_url: impl Into<String>) -> Self {
+        self.callback_url = Some(callback_url.into());
+        self
+    }
+
+    pub async fn send(self) -> Result<PaymentOrderCollectionResponse, BillplzError> {
+        let url = format!("{}/api/v5/payment_order_collections", self.client.base_url);
+
+        let epoch = epoch();
+        let checksum = self.client.x_signature()?.checksum([
+            self.title.as_str(),
+            self.callback_url.as_deref().unwrap_or_default(),
+            &epoch.to_string(),
+        ]);
+        let body = PaymentOrderCollection {
+            title: self.title,
+            callback_url: self.callback_url,
+            epoch,
+            checksum,
+        };
+
+        let request = self
+            .client
+            .http
+            .post(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+}
+
+pub struct CreatePaymentOrderBuilder<'a> {
+    client: &'a BillplzClient,
+    payment_order_collection_id: String,
+    bank_code: String,
+    bank_account_number: String,
+    name: String,
+    description: String,
+    total: Money,
+    email: Option<String>,
+    notification: Option<bool>,
+    recipient_notification: Option<bool>,
+    reference_id: Option<String>,
+}
+
+impl<'a> CreatePaymentOrderBuilder<'a> {
+    pub(crate) fn new(
+        client: &'a BillplzClient,
+        payment_order_collection_id: impl Into<String>,
+        bank_code: impl Into<String>,
+        bank_account_number: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        total: impl Into<Money>,
+    ) -> Self {
+        Self {
+            client,
+            payment_order_collection_id: payment_order_collection_id.into(),
+            bank_code: bank_code.into(),
+            bank_account_number: bank_account_number.into(),
+            name: name.into(),
+            description: description.into(),
+            total: total.into(),
+            email: None,
+            notification: None,
+            recipient_notification: None,
+            reference_id: None,
+        }
+    }
+
+    /// Recipient email, used for `recipient_notification`.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Notify the account owner when the payment order completes.
+    pub fn notification(mut self, notification: bool) -> Self {
+        self.notification = Some(notification);
+        self
+    }
+
+    /// Notify the recipient by email when the payment order completes.
+    pub fn recipient_notification(mut self, recipient_notification: bool) -> Self {
+        self.recipient_notification = Some(recipient_notification);
+        self
+    }
+
+    /// Your own reference, shown on the recipient's bank statement.
+    pub fn reference_id(mut self, reference_id: impl Into<String>) -> Self {
+        self.reference_id = Some(reference_id.into());
+        self
+    }
+
+    pub async fn send(self) -> Result<PaymentOrderResponse, BillplzError> {
+        let url = format!("{}/api/v5/payment_orders", self.client.base_url);
+
+        let epoch = epoch();
+        let checksum = self.client.x_signature()?.checksum([
+            self.payment_order_collection_id.as_str(),
+            self.bank_account_number.as_str(),
+            &self.total.cents().to_string(),
+            &epoch.to_string(),
+        ]);
+        let body = PaymentOrder {
+            payment_order_collection_id: self.payment_order_collection_id,
+            bank_code: self.bank_code,
+            bank_account_number: self.bank_account_number,
+            name: self.name,
+            description: self.description,
+            total: self.total,
+            email: self.email,
+            notification: self.notification,
+            recipient_notification: self.recipient_notification,
+            reference_id: self.reference_id,
+            epoch,
+            checksum,
+        };
+
+        let request = self
+            .client
+            .http
+            .post(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .json(&body);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+}
+
+impl BillplzClient {
+    /// GET /api/v5/payment_order_collections/{id}
+    pub async fn get_payment_order_collection(
+        &self,
+        payment_order_collection_id: &str,
+    ) -> Result<PaymentOrderCollectionResponse, BillplzError> {
+        let path = format!("payment_order_collections/{}", payment_order_collection_id);
+        self.get_signed(&path, payment_order_collection_id).await
+    }
+
+    /// POST /api/v5/payment_order_collections
+    /// Returns a builder with an optional `callback_url`.
+    pub fn create_payment_order_collection(
+        &self,
+        title: impl Into<String>,
+    ) -> CreatePaymentOrderCollectionBuilder<'_> {
+        CreatePaymentOrderCollectionBuilder::new(self, title)
+    }
+
+    /// GET /api/v5/payment_orders/{id}
+    pub async fn get_payment_order(
+        &self,
+        payment_order_id: &str,
+    ) -> Result<PaymentOrderResponse, BillplzError> {
+        let path = format!("payment_orders/{}", payment_order_id);
+        self.get_signed(&path, payment_order_id).await
+    }
+
+    /// POST /api/v5/payment_orders
+    /// Returns a builder for paying out `total` to a bank account.
+    pub fn create_payment_order(
+        &self,
+        payment_order_collection_id: impl Into<String>,
+        bank_code: impl Into<String>,
+        bank_account_number: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        total: impl Into<Money>,
+    ) -> CreatePaymentOrderBuilder<'_> {
+        CreatePaymentOrderBuilder::new(
+            self,
+            payment_order_collection_id,
+            bank_code,
+            bank_account_number,
+            name,
+            description,
+            total,
+        )
+    }
+
+    /// GET /api/v5/payment_order_limit
+    pub async fn get_payment_order_limit(&self) -> Result<PaymentOrderLimit, BillplzError> {
+        self.get_signed("payment_order_limit", "").await
+    }
+
+    /// Sends a v5 GET signed with `checksum([id, epoch])`.
+    async fn get_signed<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        id: &str,
+    ) -> Result<T, BillplzError> {
+        let url = format!("{}/api/v5/{}", self.base_url, path);
+
+        let epoch = epoch().to_string();
+        let checksum = self.x_signature()?.checksum([id, epoch.as_str()]);
+
+        let request = self
+            .http
+            .get(&url)
+            .basic_auth(&self.api_key, Option::<&str>::None)
+            .query(&[("epoch", epoch), ("checksum", checksum)]);
+
+        let resp = self.execute(request).await?;
+
+        self.parse_response(resp).await
+    }
+}
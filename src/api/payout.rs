@@ -1,7 +1,10 @@
 use crate::client::BillplzClient;
 use crate::error::BillplzError;
-use crate::models::payout::Payout;
+use crate::models::money::Money;
+use crate::models::payout::{Payout, PayoutResponse};
+use crate::validation::{self, ValidationError};
 
+#[derive(Clone)]
 pub struct CreatePayoutBuilder<'a> {
     client: &'a BillplzClient,
     mass_payment_instruction_collection_id: String,
@@ -10,7 +13,8 @@ pub struct CreatePayoutBuilder<'a> {
     identity_number: String,
     name: String,
     description: String,
-    total: i64,
+    total: Money,
+    validate_on_send: bool,
 }
 
 impl<'a> CreatePayoutBuilder<'a> {
@@ -23,7 +27,7 @@ impl<'a> CreatePayoutBuilder<'a> {
         identity_number: impl Into<String>,
         name: impl Into<String>,
         description: impl Into<String>,
-        total: i64,
+        total: impl Into<Money>,
     ) -> Self {
         Self {
             client,
@@ -33,11 +37,67 @@ impl<'a> CreatePayoutBuilder<'a> {
             identity_number: identity_number.into(),
             name: name.into(),
             description: description.into(),
-            total,
+            total: total.into(),
+            validate_on_send: true,
+        }
+    }
+
+    pub(crate) fn bank_code(&self) -> &str {
+        &self.bank_code
+    }
+
+    pub(crate) fn bank_account_number(&self) -> &str {
+        &self.bank_account_number
+    }
+
+    /// Whether `send` runs `validate` first. Defaults to true.
+    pub fn validate_on_send(mut self, enabled: bool) -> Self {
+        self.validate_on_send = enabled;
+        self
+    }
+
+    /// Checks for a SWIFT-format bank code, a 6 to 20 digit account number, a
+    /// positive total and the required name, identity number and description.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        errors.require(
+            "mass_payment_instruction_collection_id",
+            &self.mass_payment_instruction_collection_id,
+        );
+        // Format only; `create_payouts_bulk` also checks the code against the
+        // known payout banks.
+        if !validation::is_bank_code(&self.bank_code) {
+            errors.push(
+                "bank_code",
+                format!(
+                    "{:?} must be a SWIFT code of 8 or 11 letters and digits",
+                    self.bank_code
+                ),
+            );
+        }
+        if !validation::is_account_number(&self.bank_account_number) {
+            errors.push(
+                "bank_account_number",
+                format!("{:?} must be 6 to 20 digits", self.bank_account_number),
+            );
         }
+        if !self.total.is_positive() {
+            errors.push(
+                "total",
+                format!("must be greater than zero, got {}", self.total.cents()),
+            );
+        }
+        errors.require("name", &self.name);
+        errors.require("identity_number", &self.identity_number);
+        errors.require("description", &self.description);
+        errors.max_chars("description", &self.description, 200);
+        errors.finish()
     }
 
-    pub async fn send(self) -> Result<String, BillplzError> {
+    pub async fn send(self) -> Result<PayoutResponse, BillplzError> {
+        if self.validate_on_send {
+            self.validate()?;
+        }
         let url = format!("{}/api/v4/mass_payment_instructions", self.client.base_url);
 
         let body = Payout {
@@ -50,35 +110,35 @@ impl<'a> CreatePayoutBuilder<'a> {
             total: self.total,
         };
 
-        let resp = self
+        let request = self
             .client
             .http
             .post(&url)
             .basic_auth(&self.client.api_key, Option::<&str>::None)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
 
-        Ok(resp.text().await?)
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
     }
 }
 
 impl BillplzClient {
-    pub async fn get_payout(&self, payout_id: impl Into<String>) -> Result<String, BillplzError> {
+    pub async fn get_payout(&self, payout_id: impl Into<String>) -> Result<PayoutResponse, BillplzError> {
         let url = format!(
             "{}/api/v4/mass_payment_instructions/{}",
             self.base_url,
             payout_id.into()
         );
 
-        let resp = self
+        let request = self
             .http
             .get(&url)
-            .basic_auth(&self.api_key, Option::<&str>::None)
-            .send()
-            .await?;
+            .basic_auth(&self.api_key, Option::<&str>::None);
+
+        let resp = self.execute(request).await?;
 
-        Ok(resp.text().await?)
+        self.parse_response(resp).await
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -90,7 +150,7 @@ impl BillplzClient {
         identity_number: impl Into<String>,
         name: impl Into<String>,
         description: impl Into<String>,
-        total: i64,
+        total: impl Into<Money>,
     ) -> CreatePayoutBuilder<'_> {
         CreatePayoutBuilder::new(
             self,
@@ -1,6 +1,9 @@
+use crate::api::paginator::Paginator;
 use crate::client::BillplzClient;
 use crate::error::BillplzError;
-use crate::models::payout_collection::PayoutCollection;
+use crate::models::payout_collection::{
+    PayoutCollection, PayoutCollectionList, PayoutCollectionResponse,
+};
 
 pub struct CreatePayoutCollectionBuilder<'a> {
     client: &'a BillplzClient,
@@ -15,7 +18,7 @@ impl<'a> CreatePayoutCollectionBuilder<'a> {
         }
     }
 
-    pub async fn send(self) -> Result<String, BillplzError> {
+    pub async fn send(self) -> Result<PayoutCollectionResponse, BillplzError> {
         let url = format!(
             "{}/api/v4/mass_payment_instruction_collections",
             self.client.base_url
@@ -25,16 +28,70 @@ impl<'a> CreatePayoutCollectionBuilder<'a> {
             title: self.title,
         };
 
-        let resp = self
+        let request = self
             .client
             .http
             .post(&url)
             .basic_auth(&self.client.api_key, Option::<&str>::None)
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
 
-        Ok(resp.text().await?)
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+}
+
+#[derive(Clone)]
+pub struct ListPayoutCollectionsBuilder<'a> {
+    client: &'a BillplzClient,
+    page: Option<u32>,
+}
+
+impl<'a> ListPayoutCollectionsBuilder<'a> {
+    pub(crate) fn new(client: &'a BillplzClient) -> Self {
+        Self { client, page: None }
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub async fn send(self) -> Result<PayoutCollectionList, BillplzError> {
+        let url = format!(
+            "{}/api/v4/mass_payment_instruction_collections",
+            self.client.base_url
+        );
+
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+
+        let request = self
+            .client
+            .http
+            .get(&url)
+            .basic_auth(&self.client.api_key, Option::<&str>::None)
+            .query(&query);
+
+        let resp = self.client.execute(request).await?;
+
+        self.client.parse_response(resp).await
+    }
+
+    /// Streams every payout collection from `page` (default 1) onwards, fetching pages lazily.
+    pub fn paginate(self) -> Paginator<'a, PayoutCollectionResponse> {
+        let start = self.page.unwrap_or(1);
+        Paginator::new(start, move |page| {
+            let builder = self.clone().page(page);
+            async move {
+                builder
+                    .send()
+                    .await
+                    .map(|list| list.mass_payment_instruction_collections)
+            }
+        })
     }
 }
 
@@ -42,21 +99,21 @@ impl BillplzClient {
     pub async fn get_payout_collection(
         &self,
         payout_collection_id: impl Into<String>,
-    ) -> Result<String, BillplzError> {
+    ) -> Result<PayoutCollectionResponse, BillplzError> {
         let url = format!(
             "{}/api/v4/mass_payment_instruction_collections/{}",
             self.base_url,
             payout_collection_id.into()
         );
 
-        let resp = self
+        let request = self
             .http
             .get(&url)
-            .basic_auth(&self.api_key, Option::<&str>::None)
-            .send()
-            .await?;
+            .basic_auth(&self.api_key, Option::<&str>::None);
 
-        Ok(resp.text().await?)
+        let resp = self.execute(request).await?;
+
+        self.parse_response(resp).await
     }
 
     pub fn create_payout_collection(
@@ -65,4 +122,10 @@ impl BillplzClient {
     ) -> CreatePayoutCollectionBuilder<'_> {
         CreatePayoutCollectionBuilder::new(self, title)
     }
+
+    /// GET /api/v4/mass_payment_instruction_collections
+    /// Returns a builder with an optional `page`.
+    pub fn list_payout_collections(&self) -> ListPayoutCollectionsBuilder<'_> {
+        ListPayoutCollectionsBuilder::new(self)
+    }
 }
@@ -0,0 +1,270 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use tokio::time::Instant;
+
+use crate::client::BillplzClient;
+use crate::error::BillplzError;
+use crate::models::bill::{BillResponse, BillState};
+
+/// Shortest delay between polls; smaller intervals are raised to this.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Polling schedule for `wait_for_bill` and `watch_bills`.
+///
+/// The first poll happens immediately. After that the delay starts at
+/// `interval` and doubles up to `max_interval` while nothing changes.
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    /// Give up after this long.
+    pub timeout: Duration,
+    /// Delay before the second poll, at least `MIN_POLL_INTERVAL`.
+    pub interval: Duration,
+    /// Upper bound for any single delay.
+    pub max_interval: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10 * 60),
+            interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl WaitOptions {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    fn first_delay(&self) -> Duration {
+        self.interval.max(MIN_POLL_INTERVAL)
+    }
+
+    fn next_delay(&self, delay: Duration) -> Duration {
+        delay.saturating_mul(2).min(self.max_interval.max(self.first_delay()))
+    }
+}
+
+/// Whether a bill can no longer change on its own: paid, deleted, or past
+/// its `due_at` date (in Malaysian time).
+pub(crate) fn is_settled(bill: &BillResponse) -> bool {
+    bill.paid
+        || matches!(
+            bill.state,
+            BillState::Paid | BillState::Deleted | BillState::Overdue
+        )
+        || due_at_passed(&bill.due_at)
+}
+
+fn due_at_passed(due_at: &str) -> bool {
+    let Ok(due) = chrono::NaiveDate::parse_from_str(due_at.trim(), "%Y-%m-%d") else {
+        return false;
+    };
+    let myt = chrono::FixedOffset::east_opt(8 * 3600).expect("valid offset");
+    chrono::Utc::now().with_timezone(&myt).date_naive() > due
+}
+
+/// `Instant::now() + duration`, capped about 30 years out instead of
+/// overflowing on huge durations such as `Duration::MAX`.
+fn instant_after(duration: Duration) -> Instant {
+    let now = Instant::now();
+    now.checked_add(duration)
+        .unwrap_or_else(|| now + Duration::from_secs(86400 * 365 * 30))
+}
+
+/// Sleeps for `delay`, or until `deadline` if that comes first.
+async fn sleep_until_next(delay: Duration, deadline: Instant) {
+    tokio::time::sleep_until(instant_after(delay).min(deadline)).await;
+}
+
+/// A change observed by `watch_bills`.
+#[derive(Debug, Clone)]
+pub struct BillEvent {
+    /// The state before this poll, or `None` for the first observation.
+    pub previous: Option<BillState>,
+    pub bill: BillResponse,
+    /// True when this is the bill's last event (see `wait_for_bill`).
+    pub settled: bool,
+}
+
+struct Watched {
+    id: String,
+    last: Option<(BillState, bool)>,
+}
+
+/// Stream of `BillEvent`s from `watch_bills`.
+///
+/// Every bill is polled together on the `WaitOptions` schedule, and an event
+/// is yielded for the first observation of each bill and whenever its `state`
+/// or `paid` flag changes. A bill stops being watched once it is settled, or
+/// after an error fetching it, which is yielded in its place. The stream ends
+/// when no bills are left or the timeout elapses.
+pub struct BillWatch<'a> {
+    client: &'a BillplzClient,
+    ids: Vec<String>,
+    options: WaitOptions,
+    inner: Option<BoxStream<'a, Result<BillEvent, BillplzError>>>,
+}
+
+impl<'a> BillWatch<'a> {
+    pub(crate) fn new(client: &'a BillplzClient, ids: Vec<String>) -> Self {
+        Self {
+            client,
+            ids,
+            options: WaitOptions::default(),
+            inner: None,
+        }
+    }
+
+    /// Replaces the polling schedule. Has no effect once polling has started.
+    pub fn options(mut self, options: WaitOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    fn start(&mut self) -> BoxStream<'a, Result<BillEvent, BillplzError>> {
+        struct WatchState {
+            bills: Vec<Watched>,
+            events: VecDeque<Result<BillEvent, BillplzError>>,
+            delay: Option<Duration>,
+            deadline: Instant,
+        }
+
+        let client = self.client;
+        let options = self.options.clone();
+        let state = WatchState {
+            bills: std::mem::take(&mut self.ids)
+                .into_iter()
+                .map(|id| Watched { id, last: None })
+                .collect(),
+            events: VecDeque::new(),
+            delay: None,
+            deadline: instant_after(options.timeout),
+        };
+
+        futures::stream::unfold(state, move |mut state| {
+            let options = options.clone();
+            async move {
+                loop {
+                    if let Some(event) = state.events.pop_front() {
+                        return Some((event, state));
+                    }
+                    if state.bills.is_empty() || Instant::now() >= state.deadline {
+                        return None;
+                    }
+                    if let Some(delay) = state.delay {
+                        sleep_until_next(delay, state.deadline).await;
+                    }
+
+                    let fetched = futures::future::join_all(
+                        state.bills.iter().map(|b| client.get_bill(&b.id)),
+                    )
+                    .await;
+                    let mut changed = false;
+                    let mut still_watching = Vec::new();
+                    for (mut watched, result) in state.bills.drain(..).zip(fetched) {
+                        let bill = match result {
+                            Ok(bill) => bill,
+                            Err(e) => {
+                                state.events.push_back(Err(e));
+                                continue;
+                            }
+                        };
+                        let current = (bill.state.clone(), bill.paid);
+                        let settled = is_settled(&bill);
+                        if watched.last.as_ref() != Some(&current) {
+                            changed = true;
+                            state.events.push_back(Ok(BillEvent {
+                                previous: watched.last.take().map(|(state, _)| state),
+                                bill,
+                                settled,
+                            }));
+                        }
+                        if !settled {
+                            watched.last = Some(current);
+                            still_watching.push(watched);
+                        }
+                    }
+                    state.bills = still_watching;
+                    state.delay = Some(match state.delay {
+                        Some(delay) if !changed => options.next_delay(delay),
+                        _ => options.first_delay(),
+                    });
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+impl Stream for BillWatch<'_> {
+    type Item = Result<BillEvent, BillplzError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.inner.is_none() {
+            this.inner = Some(this.start());
+        }
+        this.inner
+            .as_mut()
+            .expect("started above")
+            .poll_next_unpin(cx)
+    }
+}
+
+impl BillplzClient {
+    /// Polls `get_bill` until the bill is paid, deleted or past its `due_at`
+    /// date, and returns it. Check `paid` on the result: a deleted or expired
+    /// bill is returned too. Fails with `BillplzError::WaitTimeout` if the bill
+    /// is still open after `options.timeout`.
+    pub async fn wait_for_bill(
+        &self,
+        bill_id: impl Into<String>,
+        options: WaitOptions,
+    ) -> Result<BillResponse, BillplzError> {
+        let bill_id = bill_id.into();
+        let deadline = instant_after(options.timeout);
+        let mut delay = options.first_delay();
+        loop {
+            let bill = self.get_bill(&bill_id).await?;
+            if is_settled(&bill) {
+                return Ok(bill);
+            }
+            if Instant::now() >= deadline {
+                return Err(BillplzError::WaitTimeout {
+                    bill_id,
+                    state: bill.state.to_string(),
+                });
+            }
+            sleep_until_next(delay, deadline).await;
+            delay = options.next_delay(delay);
+        }
+    }
+
+    /// Watches several bills at once; see `BillWatch`.
+    pub fn watch_bills<I, S>(&self, bill_ids: I) -> BillWatch<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        BillWatch::new(self, bill_ids.into_iter().map(Into::into).collect())
+    }
+}
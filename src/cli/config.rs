@@ -1,16 +1,27 @@
 use serde::Deserialize;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Default)]
 struct FileConfig {
     api_key: Option<String>,
     environment: Option<String>,
+    x_signature_key: Option<String>,
+    idempotency_store: Option<PathBuf>,
+    base_url: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct Config {
     pub api_key: String,
     pub environment: String,
+    pub x_signature_key: Option<String>,
+    /// JSON file recording bills created with `--idempotency-key`. The file
+    /// actually used is namespaced per API URL and key; see
+    /// `idempotency_store_path`.
+    pub idempotency_store: Option<PathBuf>,
+    /// Overrides the environment's API URL, e.g. to target `billplz simulate`.
+    pub base_url: Option<String>,
 }
 
 impl Config {
@@ -36,14 +47,81 @@ impl Config {
             .or(file_config.environment)
             .unwrap_or_else(|| "staging".to_string());
 
-        Ok(Config { api_key, environment })
+        let x_signature_key = std::env::var("BILLPLZ_X_SIGNATURE_KEY")
+            .ok()
+            .or(file_config.x_signature_key);
+
+        let idempotency_store = std::env::var_os("BILLPLZ_IDEMPOTENCY_STORE")
+            .map(PathBuf::from)
+            .or(file_config.idempotency_store)
+            .or_else(|| {
+                dirs::home_dir().map(|h| h.join(".billplz").join("idempotency.json"))
+            });
+
+        let base_url = std::env::var("BILLPLZ_BASE_URL")
+            .ok()
+            .or(file_config.base_url);
+
+        Ok(Config {
+            api_key,
+            environment,
+            x_signature_key,
+            idempotency_store,
+            base_url,
+        })
     }
 
-    pub fn into_client(self) -> crate::BillplzClient {
-        let env = match self.environment.as_str() {
+    fn parsed_environment(&self) -> crate::Environment {
+        match self.environment.as_str() {
             "production" => crate::Environment::Production,
             _ => crate::Environment::Staging,
+        }
+    }
+
+    /// The idempotency store file for this API URL and key.
+    ///
+    /// A key recorded against staging must not return the staging bill on a
+    /// production run, so `keys.json` becomes `keys.<hash>.json`, where the
+    /// hash covers the base URL and the API key.
+    pub fn idempotency_store_path(&self) -> Option<PathBuf> {
+        let path = self.idempotency_store.as_ref()?;
+        let env = self.parsed_environment();
+        let base_url = self
+            .base_url
+            .as_deref()
+            .unwrap_or(env.base_url())
+            .trim_end_matches('/');
+        let digest = Sha256::new()
+            .chain_update(base_url)
+            .chain_update("\n")
+            .chain_update(&self.api_key)
+            .finalize();
+        let namespace = hex::encode(&digest[..8]);
+
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = match path.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, namespace, ext.to_string_lossy()),
+            None => format!("{}.{}", stem, namespace),
         };
-        crate::BillplzClient::new(env, self.api_key)
+        Some(path.with_file_name(file_name))
+    }
+
+    /// Builds the client, failing with `BillplzError::Config` on a bad `base_url`.
+    pub fn into_client(self) -> Result<crate::BillplzClient, crate::BillplzError> {
+        let env = self.parsed_environment();
+        let store_path = self.idempotency_store_path();
+        let mut builder = crate::BillplzClient::builder()
+            .environment(env)
+            .api_key(self.api_key);
+        if let Some(base_url) = self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(key) = self.x_signature_key {
+            builder = builder.x_signature_key(key);
+        }
+        if let Some(path) = store_path {
+            builder = builder.idempotency_store(crate::FileIdempotencyStore::new(path));
+        }
+        builder.build()
     }
 }
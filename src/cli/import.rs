@@ -0,0 +1,465 @@
+//! File-driven bulk imports: `billplz bill import` and `billplz payout import`.
+//!
+//! Input is CSV (with a header row) or a JSON array of objects, chosen by file
+//! extension. Every processed row is appended to a CSV results file as soon as
+//! it completes, so an interrupted import can be resumed from it.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api::bulk::BulkPayoutOptions;
+use crate::{BillplzClient, BillplzError};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// One bill in an import file. Amounts are in sen.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BillRow {
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    pub email: String,
+    pub name: String,
+    pub amount: i64,
+    pub description: String,
+    pub due_at: String,
+    #[serde(default)]
+    pub callback_url: Option<String>,
+    #[serde(default)]
+    pub mobile: Option<String>,
+    #[serde(default)]
+    pub redirect_url: Option<String>,
+    #[serde(default)]
+    pub reference_1_label: Option<String>,
+    #[serde(default)]
+    pub reference_1: Option<String>,
+    #[serde(default)]
+    pub reference_2_label: Option<String>,
+    #[serde(default)]
+    pub reference_2: Option<String>,
+    /// Makes re-importing the row return the bill already created for it.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RowStatus {
+    Created,
+    Failed,
+    /// Not attempted, e.g. an unverified bank account. Retried on `--resume`.
+    Skipped,
+    /// The request got no answer, so it may have gone through. Never retried
+    /// on `--resume`; check the row on Billplz by hand.
+    Unknown,
+}
+
+/// One line of the results file. `row` is the 1-based position in the input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowResult {
+    pub row: usize,
+    pub status: RowStatus,
+    pub id: Option<String>,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RowResult {
+    pub fn created(row: usize, id: impl Into<String>, url: Option<String>) -> Self {
+        Self {
+            row,
+            status: RowStatus::Created,
+            id: Some(id.into()),
+            url,
+            error: None,
+        }
+    }
+
+    pub fn failed(row: usize, error: impl ToString) -> Self {
+        Self {
+            row,
+            status: RowStatus::Failed,
+            id: None,
+            url: None,
+            error: Some(error.to_string()),
+        }
+    }
+
+    /// A row whose send failed. Sends that got no usable answer are marked
+    /// unknown, since the bill may have been created.
+    pub fn send_failed(row: usize, error: &BillplzError) -> Self {
+        Self {
+            status: if is_unanswered(error) {
+                RowStatus::Unknown
+            } else {
+                RowStatus::Failed
+            },
+            ..Self::failed(row, error)
+        }
+    }
+}
+
+/// Whether a send failed without a usable answer from Billplz, so whatever it
+/// asked for may have happened anyway.
+fn is_unanswered(error: &BillplzError) -> bool {
+    error.is_retryable()
+        || matches!(
+            error,
+            BillplzError::Http(_) | BillplzError::OutcomeUnknown { .. }
+        )
+}
+
+/// Counts printed once an import finishes.
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub unknown: usize,
+    /// Rows already marked created in the results file when resuming.
+    pub already_created: usize,
+    /// Rows marked unknown, in this run or an earlier one. Check them on
+    /// Billplz; to have `--resume` send one again, delete its lines from the
+    /// results file.
+    pub needs_review: Vec<usize>,
+    pub results: PathBuf,
+}
+
+impl ImportSummary {
+    fn new(results: &Path) -> Self {
+        Self {
+            results: results.to_path_buf(),
+            ..Self::default()
+        }
+    }
+
+    fn count(&mut self, status: RowStatus) {
+        match status {
+            RowStatus::Created => self.created += 1,
+            RowStatus::Failed => self.failed += 1,
+            RowStatus::Skipped => self.skipped += 1,
+            RowStatus::Unknown => self.unknown += 1,
+        }
+    }
+
+    /// Accounts for a row that `--resume` does not send again.
+    fn resumed(&mut self, row_no: usize, status: RowStatus) {
+        if status == RowStatus::Unknown {
+            self.needs_review.push(row_no);
+        } else {
+            self.already_created += 1;
+        }
+    }
+}
+
+/// Reads rows from a `.json` array or, for any other extension, a CSV file.
+pub fn read_rows<T: DeserializeOwned>(path: &Path) -> CliResult<Vec<T>> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        let file = File::open(path)?;
+        return Ok(serde_json::from_reader(std::io::BufReader::new(file))?);
+    }
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(path)?;
+    let mut rows = Vec::new();
+    for (i, record) in reader.deserialize().enumerate() {
+        rows.push(record.map_err(|e| format!("row {}: {}", i + 1, e))?);
+    }
+    Ok(rows)
+}
+
+/// Default results file: `bills.csv` -> `bills.results.csv`.
+pub fn default_results_path(input: &Path) -> PathBuf {
+    input.with_extension("results.csv")
+}
+
+/// Rows that `--resume` must not send again: their latest entry in an
+/// existing results file is `created`, or `unknown` because an earlier
+/// attempt got no answer.
+pub fn resume_rows(results: &Path) -> CliResult<HashMap<usize, RowStatus>> {
+    if !results.exists() {
+        return Ok(HashMap::new());
+    }
+    // Only these two columns are shared by the bill and payout reports.
+    #[derive(Deserialize)]
+    struct RowState {
+        row: usize,
+        status: RowStatus,
+    }
+
+    let mut latest: HashMap<usize, RowStatus> = HashMap::new();
+    for record in csv::Reader::from_path(results)?.deserialize() {
+        let record: RowState = record?;
+        latest.insert(record.row, record.status);
+    }
+    latest.retain(|_, status| matches!(status, RowStatus::Created | RowStatus::Unknown));
+    Ok(latest)
+}
+
+/// Appends results, flushing after every row.
+pub struct ResultsWriter {
+    writer: csv::Writer<File>,
+}
+
+impl ResultsWriter {
+    /// Starts a new results file, or appends to it when `append` is set.
+    pub fn open(path: &Path, append: bool) -> CliResult<Self> {
+        let has_content = append && std::fs::metadata(path).is_ok_and(|m| m.len() > 0);
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
+        let writer = csv::WriterBuilder::new()
+            .has_headers(!has_content)
+            .from_writer(file);
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, result: &impl Serialize) -> CliResult<()> {
+        self.writer.serialize(result)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Settings shared by every row of a bill import.
+pub struct BillImport {
+    pub collection_id: Option<String>,
+    pub callback_url: Option<String>,
+    pub concurrency: usize,
+    pub results: PathBuf,
+    pub resume: bool,
+}
+
+impl BillImport {
+    pub async fn run(&self, client: &BillplzClient, rows: Vec<BillRow>) -> CliResult<ImportSummary> {
+        let done = if self.resume {
+            resume_rows(&self.results)?
+        } else {
+            HashMap::new()
+        };
+        let mut writer = ResultsWriter::open(&self.results, self.resume)?;
+        let mut summary = ImportSummary::new(&self.results);
+
+        let mut pending = Vec::new();
+        let mut builders = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let row_no = i + 1;
+            if let Some(&status) = done.get(&row_no) {
+                summary.resumed(row_no, status);
+                continue;
+            }
+            match self.builder(client, row) {
+                Ok(builder) => {
+                    pending.push(row_no);
+                    builders.push(builder);
+                }
+                Err(e) => {
+                    writer.write(&RowResult::failed(row_no, e))?;
+                    summary.count(RowStatus::Failed);
+                }
+            }
+        }
+
+        let mut outcomes = client.create_bills_bulk(builders, self.concurrency);
+        while let Some(outcome) = outcomes.next().await {
+            let row_no = pending[outcome.index];
+            let result = match outcome.result {
+                Ok(bill) => RowResult::created(row_no, bill.id, bill.url),
+                Err(e) => RowResult::send_failed(row_no, &e),
+            };
+            summary.count(result.status);
+            if result.status == RowStatus::Unknown {
+                summary.needs_review.push(row_no);
+            }
+            writer.write(&result)?;
+        }
+        summary.needs_review.sort_unstable();
+        Ok(summary)
+    }
+
+    fn builder<'a>(
+        &self,
+        client: &'a BillplzClient,
+        row: BillRow,
+    ) -> Result<crate::api::bill::CreateBillBuilder<'a>, String> {
+        let collection_id = row
+            .collection_id
+            .or_else(|| self.collection_id.clone())
+            .ok_or("no collection_id in the row and no --collection-id given")?;
+        let callback_url = row
+            .callback_url
+            .or_else(|| self.callback_url.clone())
+            .ok_or("no callback_url in the row and no --callback-url given")?;
+
+        let mut builder = client.create_bill(
+            collection_id,
+            row.email,
+            row.name,
+            row.amount,
+            callback_url,
+            row.description,
+            row.due_at,
+        );
+        if let Some(m) = row.mobile {
+            builder = builder.mobile(m);
+        }
+        if let Some(r) = row.redirect_url {
+            builder = builder.redirect_url(r);
+        }
+        if let Some(l) = row.reference_1_label {
+            builder = builder.reference_1_label(l);
+        }
+        if let Some(v) = row.reference_1 {
+            builder = builder.reference_1(v);
+        }
+        if let Some(l) = row.reference_2_label {
+            builder = builder.reference_2_label(l);
+        }
+        if let Some(v) = row.reference_2 {
+            builder = builder.reference_2(v);
+        }
+        if let Some(k) = row.idempotency_key {
+            builder = builder.idempotency_key(k);
+        }
+        Ok(builder)
+    }
+}
+
+/// One payout in an import file. `total` is in sen.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PayoutRow {
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    /// SWIFT code of the recipient's bank, e.g. `MBBEMYKL`.
+    pub bank_code: String,
+    pub bank_account_number: String,
+    pub identity_number: String,
+    pub name: String,
+    pub description: String,
+    pub total: i64,
+}
+
+/// One line of the payout reconciliation report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutRowResult {
+    pub row: usize,
+    pub status: RowStatus,
+    pub id: Option<String>,
+    pub bank_code: String,
+    pub bank_account_number: String,
+    pub name: String,
+    pub total: i64,
+    /// Billplz status of the created payout, e.g. `enqueued`.
+    pub payout_status: Option<String>,
+    pub error: Option<String>,
+}
+
+impl PayoutRowResult {
+    fn new(row_no: usize, row: &PayoutRow, status: RowStatus) -> Self {
+        Self {
+            row: row_no,
+            status,
+            id: None,
+            bank_code: row.bank_code.clone(),
+            bank_account_number: row.bank_account_number.clone(),
+            name: row.name.clone(),
+            total: row.total,
+            payout_status: None,
+            error: None,
+        }
+    }
+
+    fn failed(row_no: usize, row: &PayoutRow, error: &BillplzError) -> Self {
+        let status = match error {
+            BillplzError::UnverifiedAccount { .. } => RowStatus::Skipped,
+            // No usable answer: the payout may have been created.
+            e if is_unanswered(e) => RowStatus::Unknown,
+            _ => RowStatus::Failed,
+        };
+        Self {
+            error: Some(error.to_string()),
+            ..Self::new(row_no, row, status)
+        }
+    }
+}
+
+/// Settings shared by every row of a payout import.
+pub struct PayoutImport {
+    pub collection_id: Option<String>,
+    pub concurrency: usize,
+    pub require_verified: bool,
+    pub results: PathBuf,
+    pub resume: bool,
+}
+
+impl PayoutImport {
+    pub async fn run(&self, client: &BillplzClient, rows: Vec<PayoutRow>) -> CliResult<ImportSummary> {
+        let done = if self.resume {
+            resume_rows(&self.results)?
+        } else {
+            HashMap::new()
+        };
+        let mut writer = ResultsWriter::open(&self.results, self.resume)?;
+        let mut summary = ImportSummary::new(&self.results);
+
+        let mut pending = Vec::new();
+        let mut builders = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let row_no = i + 1;
+            if let Some(&status) = done.get(&row_no) {
+                summary.resumed(row_no, status);
+                continue;
+            }
+            let Some(collection_id) = row.collection_id.clone().or_else(|| self.collection_id.clone())
+            else {
+                let error = BillplzError::InvalidPayload(
+                    "no collection_id in the row and no --collection-id given".into(),
+                );
+                writer.write(&PayoutRowResult::failed(row_no, &row, &error))?;
+                summary.count(RowStatus::Failed);
+                continue;
+            };
+            builders.push(client.create_payout(
+                collection_id,
+                row.bank_code.as_str(),
+                row.bank_account_number.as_str(),
+                row.identity_number.as_str(),
+                row.name.as_str(),
+                row.description.as_str(),
+                row.total,
+            ));
+            pending.push((row_no, row));
+        }
+
+        let options = BulkPayoutOptions::default()
+            .concurrency(self.concurrency)
+            .require_verified(self.require_verified);
+        let mut outcomes = client.create_payouts_bulk(builders, options);
+        while let Some(outcome) = outcomes.next().await {
+            let (row_no, row) = &pending[outcome.index];
+            let result = match outcome.result {
+                Ok(payout) => PayoutRowResult {
+                    id: Some(payout.id),
+                    payout_status: Some(payout.status.as_str().to_string()),
+                    ..PayoutRowResult::new(*row_no, row, RowStatus::Created)
+                },
+                Err(e) => PayoutRowResult::failed(*row_no, row, &e),
+            };
+            summary.count(result.status);
+            if result.status == RowStatus::Unknown {
+                summary.needs_review.push(*row_no);
+            }
+            writer.write(&result)?;
+        }
+        summary.needs_review.sort_unstable();
+        Ok(summary)
+    }
+}
@@ -1,4 +1,6 @@
 pub mod config;
+pub mod import;
+pub mod webhook;
 
 use clap::{Parser, Subcommand};
 
@@ -20,11 +22,21 @@ pub enum Commands {
         #[command(subcommand)]
         action: CollectionAction,
     },
+    /// Manage open collections (payment forms)
+    OpenCollection {
+        #[command(subcommand)]
+        action: OpenCollectionAction,
+    },
     /// Manage bills
     Bill {
         #[command(subcommand)]
         action: BillAction,
     },
+    /// Manage tokenized cards
+    Card {
+        #[command(subcommand)]
+        action: CardAction,
+    },
     /// Bank operations
     Bank {
         #[command(subcommand)]
@@ -40,6 +52,48 @@ pub enum Commands {
         #[command(subcommand)]
         action: PayoutCollectionAction,
     },
+    /// Manage payment orders (v5 payouts)
+    PaymentOrder {
+        #[command(subcommand)]
+        action: PaymentOrderAction,
+    },
+    /// Manage payment order collections
+    PaymentOrderCollection {
+        #[command(subcommand)]
+        action: PaymentOrderCollectionAction,
+    },
+    /// Receive Billplz callbacks locally
+    Webhook {
+        #[command(subcommand)]
+        action: WebhookAction,
+    },
+    /// Compare a local ledger of bills against Billplz
+    Reconcile {
+        /// CSV with a header row, or a .json array: bill_id, paid, and optionally amount (in sen), state, paid_at
+        #[arg(long)]
+        input: std::path::PathBuf,
+        /// Maximum number of bills fetched in parallel
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Print one CSV row per ledger entry instead of the JSON report
+        #[arg(long)]
+        csv: bool,
+        /// Write the report to this file instead of stdout
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Run an in-memory Billplz API for local testing
+    #[cfg(feature = "sim")]
+    Simulate {
+        #[arg(long, default_value_t = 8090)]
+        port: u16,
+        /// API key clients must send as the Basic auth username
+        #[arg(long, env = "BILLPLZ_API_KEY", hide_env_values = true)]
+        api_key: String,
+        /// Sign callbacks with this key
+        #[arg(long, env = "BILLPLZ_X_SIGNATURE_KEY", hide_env_values = true)]
+        x_signature_key: Option<String>,
+    },
     /// Start MCP server (stdio transport)
     Mcp,
 }
@@ -55,6 +109,79 @@ pub enum CollectionAction {
         #[arg(long)]
         split_header: bool,
     },
+    /// List collections
+    List {
+        #[arg(long)]
+        page: Option<u32>,
+        /// Fetch every page from --page (default 1) onwards
+        #[arg(long)]
+        all: bool,
+        /// Filter by status (active, inactive)
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Activate a collection
+    Activate { id: String },
+    /// Deactivate a collection
+    Deactivate { id: String },
+    /// Show or set the payment methods enabled on a collection
+    PaymentMethods {
+        id: String,
+        /// Comma-separated payment method codes to enable (e.g. fpx,creditcard)
+        #[arg(long, value_delimiter = ',')]
+        set: Option<Vec<String>>,
+    },
+    /// List payment gateways available to the account
+    PaymentGateways,
+}
+
+#[derive(Subcommand)]
+pub enum OpenCollectionAction {
+    /// Get an open collection by ID
+    Get { id: String },
+    /// Create a new open collection
+    Create {
+        #[arg(long)]
+        title: String,
+        #[arg(long)]
+        description: String,
+        /// Fixed amount in cents
+        #[arg(long)]
+        amount: Option<i64>,
+        /// Let payers enter their own amount
+        #[arg(long)]
+        open_amount: bool,
+        /// Let payers choose a quantity
+        #[arg(long)]
+        open_quantity: bool,
+        /// Button label (pay, buy)
+        #[arg(long)]
+        payment_button: Option<String>,
+        #[arg(long)]
+        reference_1_label: Option<String>,
+        #[arg(long)]
+        reference_2_label: Option<String>,
+        #[arg(long)]
+        redirect_uri: Option<String>,
+        /// Path to an image shown on the payment form
+        #[arg(long)]
+        photo: Option<std::path::PathBuf>,
+    },
+    /// List open collections
+    List {
+        #[arg(long)]
+        page: Option<u32>,
+        /// Fetch every page from --page (default 1) onwards
+        #[arg(long)]
+        all: bool,
+        /// Filter by status (active, inactive)
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Activate an open collection
+    Activate { id: String },
+    /// Deactivate an open collection
+    Deactivate { id: String },
 }
 
 #[derive(Subcommand)]
@@ -90,13 +217,122 @@ pub enum BillAction {
         reference_2_label: Option<String>,
         #[arg(long)]
         reference_2: Option<String>,
+        /// FPX bank code to preselect (sets reference 1 to "Bank Code")
+        #[arg(long)]
+        preferred_bank: Option<String>,
+        /// Return the bill already created with this key instead of creating another (stored in reference 2)
+        #[arg(long)]
+        idempotency_key: Option<String>,
+    },
+    /// Delete an unpaid bill
+    Delete { id: String },
+    /// Create bills from a CSV or JSON file
+    Import {
+        /// CSV with a header row, or a .json array of bill objects (amounts in sen)
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Collection for rows without a collection_id column
+        #[arg(long)]
+        collection_id: Option<String>,
+        /// Callback URL for rows without a callback_url column
+        #[arg(long)]
+        callback_url: Option<String>,
+        /// Maximum number of bills created in parallel
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Results CSV (default: <file>.results.csv)
+        #[arg(long)]
+        results: Option<std::path::PathBuf>,
+        /// Skip rows already marked created in the results file and append to it
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Poll a bill until it is paid, deleted or past due; exits non-zero unless paid
+    Wait {
+        id: String,
+        /// Give up after this long (e.g. 90s, 10m, 1h)
+        #[arg(long, default_value = "10m", value_parser = parse_duration)]
+        timeout: std::time::Duration,
+        /// Delay before the second poll; doubles while nothing changes
+        #[arg(long, default_value = "2s", value_parser = parse_interval)]
+        interval: std::time::Duration,
+    },
+    /// List payment transactions for a bill
+    Transactions {
+        id: String,
+        #[arg(long)]
+        page: Option<u32>,
+        /// Fetch every page from --page (default 1) onwards
+        #[arg(long)]
+        all: bool,
+        /// Filter by status (pending, completed, failed)
+        #[arg(long)]
+        status: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CardAction {
+    /// Tokenize a card. The card number and CVV are prompted for without
+    /// echo, or read as two lines from stdin when it is not a terminal.
+    Create {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        phone: String,
+        /// Expiry as MM/YY
+        #[arg(long)]
+        expiry: String,
+    },
+    /// Delete a tokenized card
+    Delete {
+        id: String,
+        #[arg(long)]
+        token: String,
+    },
+    /// Charge a bill to a tokenized card
+    Charge {
+        bill_id: String,
+        #[arg(long)]
+        card_id: String,
+        #[arg(long)]
+        token: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WebhookAction {
+    /// Run a local server that verifies and prints bill callbacks
+    Listen {
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Address to bind; use 0.0.0.0 to accept callbacks from other hosts
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: std::net::IpAddr,
+        #[arg(long, env = "BILLPLZ_X_SIGNATURE_KEY", hide_env_values = true)]
+        x_signature_key: String,
+        /// Re-POST verified callbacks to this URL
+        #[arg(long)]
+        forward_to: Option<String>,
+        /// Print one JSON object per line instead of indented JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum BankAction {
     /// List FPX banks
-    FpxList,
+    FpxList {
+        /// Fetch the current list from Billplz instead of the built-in table
+        #[arg(long)]
+        live: bool,
+        /// Only show banks currently accepting payments
+        #[arg(long)]
+        only_active: bool,
+    },
     /// Get bank account verification status
     Verify { account_number: String },
     /// Create a bank account verification
@@ -135,6 +371,27 @@ pub enum PayoutAction {
         #[arg(long)]
         total: i64,
     },
+    /// Create payouts from a CSV or JSON file after pre-flight checks
+    Import {
+        /// CSV with a header row, or a .json array of payout objects (totals in sen)
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Payout collection for rows without a collection_id column
+        #[arg(long)]
+        collection_id: Option<String>,
+        /// Maximum number of payouts created in parallel
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Skip accounts whose bank verification is not "verified"
+        #[arg(long)]
+        require_verified: bool,
+        /// Reconciliation report CSV (default: <file>.results.csv)
+        #[arg(long)]
+        results: Option<std::path::PathBuf>,
+        /// Skip rows already marked created in the report and append to it
+        #[arg(long)]
+        resume: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -146,6 +403,116 @@ pub enum PayoutCollectionAction {
         #[arg(long)]
         title: String,
     },
+    /// List payout collections
+    List {
+        #[arg(long)]
+        page: Option<u32>,
+        /// Fetch every page from --page (default 1) onwards
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PaymentOrderAction {
+    /// Get a payment order by ID
+    Get { id: String },
+    /// Create a payment order
+    Create {
+        #[arg(long)]
+        collection_id: String,
+        #[arg(long)]
+        bank_code: String,
+        #[arg(long)]
+        acc_no: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        description: String,
+        /// Amount in cents
+        #[arg(long)]
+        total: i64,
+        #[arg(long)]
+        email: Option<String>,
+        /// Email the recipient when the payment completes
+        #[arg(long)]
+        recipient_notification: bool,
+        #[arg(long)]
+        reference_id: Option<String>,
+    },
+    /// Show the remaining payment order limit
+    Limit,
+}
+
+#[derive(Subcommand)]
+pub enum PaymentOrderCollectionAction {
+    /// Get a payment order collection by ID
+    Get { id: String },
+    /// Create a payment order collection
+    Create {
+        #[arg(long)]
+        title: String,
+        #[arg(long)]
+        callback_url: Option<String>,
+    },
+}
+
+/// Longest duration `parse_duration` accepts.
+const MAX_DURATION: std::time::Duration = std::time::Duration::from_secs(30 * 24 * 3600);
+
+/// Parses `500ms`, `90s`, `10m`, `1h` or a bare number of seconds, up to 30 days.
+fn parse_duration(s: &str) -> Result<std::time::Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration {:?}", s))?;
+    let millis = match unit {
+        "ms" => Some(number),
+        "" | "s" => number.checked_mul(1000),
+        "m" => number.checked_mul(60 * 1000),
+        "h" => number.checked_mul(3600 * 1000),
+        _ => return Err(format!("invalid duration unit {:?} (use ms, s, m or h)", unit)),
+    };
+    millis
+        .map(std::time::Duration::from_millis)
+        .filter(|duration| *duration <= MAX_DURATION)
+        .ok_or_else(|| format!("duration {:?} is longer than 30 days", s))
+}
+
+/// Like `parse_duration`, but a zero interval would poll in a tight loop.
+fn parse_interval(s: &str) -> Result<std::time::Duration, String> {
+    let interval = parse_duration(s)?;
+    if interval.is_zero() {
+        return Err("interval must be greater than zero".into());
+    }
+    Ok(interval)
+}
+
+/// Reads the card number and CVV so they never appear in argv or shell history.
+fn read_card_secrets() -> std::io::Result<(String, String)> {
+    use std::io::{BufRead, IsTerminal};
+
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        let card_number = rpassword::prompt_password("Card number: ")?;
+        let cvv = rpassword::prompt_password("CVV: ")?;
+        return Ok((card_number.trim().to_string(), cvv.trim().to_string()));
+    }
+    let mut lines = stdin.lock().lines();
+    let mut next = |what: &str| -> std::io::Result<String> {
+        match lines.next() {
+            Some(line) => Ok(line?.trim().to_string()),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("expected the {} on stdin", what),
+            )),
+        }
+    };
+    let card_number = next("card number")?;
+    let cvv = next("CVV")?;
+    Ok((card_number, cvv))
 }
 
 fn output_json(value: &impl serde::Serialize, pretty: bool) {
@@ -162,12 +529,43 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
     match &cli.command {
         Commands::Mcp => {
             let config = config::Config::load(None)?;
-            let client = config.into_client();
+            let client = config.into_client()?;
             crate::mcp::start_mcp_server(client).await
         }
+        Commands::Webhook {
+            action:
+                WebhookAction::Listen {
+                    port,
+                    bind,
+                    x_signature_key,
+                    forward_to,
+                    json,
+                },
+        } => {
+            let mut listener = webhook::WebhookListener::new(x_signature_key).bind_address(*bind);
+            if let Some(url) = forward_to {
+                listener = listener.forward_to(url);
+            }
+            if *json {
+                listener = listener.format(webhook::OutputFormat::JsonLines);
+            }
+            listener.listen(*port).await
+        }
+        #[cfg(feature = "sim")]
+        Commands::Simulate {
+            port,
+            api_key,
+            x_signature_key,
+        } => {
+            let mut simulator = crate::Simulator::new(api_key);
+            if let Some(key) = x_signature_key {
+                simulator = simulator.x_signature_key(key);
+            }
+            simulator.listen(*port).await
+        }
         _ => {
             let config = config::Config::load(None)?;
-            let client = config.into_client();
+            let client = config.into_client()?;
             execute_command(&cli, &client).await
         }
     }
@@ -191,6 +589,116 @@ async fn execute_command(
                 let result = builder.send().await?;
                 output_json(&result, cli.pretty);
             }
+            CollectionAction::List { page, all, status } => {
+                let mut builder = client.list_collections();
+                if let Some(p) = page {
+                    builder = builder.page(*p);
+                }
+                if let Some(s) = status {
+                    builder = builder.status(s);
+                }
+                if *all {
+                    let result = builder.paginate().collect_all().await?;
+                    output_json(&result, cli.pretty);
+                } else {
+                    let result = builder.send().await?;
+                    output_json(&result, cli.pretty);
+                }
+            }
+            CollectionAction::Activate { id } => {
+                client.activate_collection(id).await?;
+                output_json(&serde_json::json!({ "id": id, "status": "active" }), cli.pretty);
+            }
+            CollectionAction::Deactivate { id } => {
+                client.deactivate_collection(id).await?;
+                output_json(&serde_json::json!({ "id": id, "status": "inactive" }), cli.pretty);
+            }
+            CollectionAction::PaymentMethods { id, set } => {
+                let result = match set {
+                    Some(codes) => client.update_payment_methods(id, codes).await?,
+                    None => client.get_payment_methods(id).await?,
+                };
+                output_json(&result, cli.pretty);
+            }
+            CollectionAction::PaymentGateways => {
+                let result = client.get_payment_gateways().await?;
+                output_json(&result, cli.pretty);
+            }
+        },
+        Commands::OpenCollection { action } => match action {
+            OpenCollectionAction::Get { id } => {
+                let result = client.get_open_collection(id).await?;
+                output_json(&result, cli.pretty);
+            }
+            OpenCollectionAction::Create {
+                title,
+                description,
+                amount,
+                open_amount,
+                open_quantity,
+                payment_button,
+                reference_1_label,
+                reference_2_label,
+                redirect_uri,
+                photo,
+            } => {
+                let mut builder = client.create_open_collection(title, description);
+                if let Some(a) = amount {
+                    builder = builder.amount(*a);
+                }
+                if *open_amount {
+                    builder = builder.fixed_amount(false);
+                }
+                if *open_quantity {
+                    builder = builder.fixed_quantity(false);
+                }
+                if let Some(b) = payment_button {
+                    builder = builder.payment_button(b);
+                }
+                if let Some(l) = reference_1_label {
+                    builder = builder.reference_1_label(l);
+                }
+                if let Some(l) = reference_2_label {
+                    builder = builder.reference_2_label(l);
+                }
+                if let Some(r) = redirect_uri {
+                    builder = builder.redirect_uri(r);
+                }
+                if let Some(path) = photo {
+                    let bytes = std::fs::read(path)?;
+                    let file_name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "photo".to_string());
+                    builder = builder.photo(bytes, file_name);
+                }
+                let result = builder.send().await?;
+                output_json(&result, cli.pretty);
+            }
+            OpenCollectionAction::List { page, all, status } => {
+                let mut builder = client.list_open_collections();
+                if let Some(p) = page {
+                    builder = builder.page(*p);
+                }
+                if let Some(s) = status {
+                    builder = builder.status(s);
+                }
+                if *all {
+                    let result = builder.paginate().collect_all().await?;
+                    output_json(&result, cli.pretty);
+                } else {
+                    let result = builder.send().await?;
+                    output_json(&result, cli.pretty);
+                }
+            }
+            OpenCollectionAction::Activate { id } => {
+                client.activate_open_collection(id).await?;
+                output_json(&serde_json::json!({ "id": id, "status": "active" }), cli.pretty);
+            }
+            OpenCollectionAction::Deactivate { id } => {
+                client.deactivate_open_collection(id).await?;
+                output_json(&serde_json::json!({ "id": id, "status": "inactive" }), cli.pretty);
+            }
         },
         Commands::Bill { action } => match action {
             BillAction::Get { id } => {
@@ -211,6 +719,8 @@ async fn execute_command(
                 reference_1,
                 reference_2_label,
                 reference_2,
+                preferred_bank,
+                idempotency_key,
             } => {
                 let mut builder = client.create_bill(
                     collection_id,
@@ -239,20 +749,125 @@ async fn execute_command(
                 if let Some(v) = reference_2 {
                     builder = builder.reference_2(v);
                 }
+                if let Some(b) = preferred_bank {
+                    builder = builder.preferred_bank(b.as_str());
+                }
+                if let Some(k) = idempotency_key {
+                    builder = builder.idempotency_key(k);
+                }
                 let result = builder.send().await?;
                 output_json(&result, cli.pretty);
             }
+            BillAction::Delete { id } => {
+                client.delete_bill(id).await?;
+                output_json(&serde_json::json!({ "id": id, "deleted": true }), cli.pretty);
+            }
+            BillAction::Import {
+                file,
+                collection_id,
+                callback_url,
+                concurrency,
+                results,
+                resume,
+            } => {
+                let rows: Vec<import::BillRow> = import::read_rows(file)?;
+                let import = import::BillImport {
+                    collection_id: collection_id.clone(),
+                    callback_url: callback_url.clone(),
+                    concurrency: *concurrency,
+                    results: results
+                        .clone()
+                        .unwrap_or_else(|| import::default_results_path(file)),
+                    resume: *resume,
+                };
+                let summary = import.run(client, rows).await?;
+                output_json(&summary, cli.pretty);
+                if !summary.needs_review.is_empty() {
+                    eprintln!(
+                        "warning: rows {:?} got no answer from Billplz and may have been created. \
+                         Check them on Billplz; --resume will not send them again.",
+                        summary.needs_review
+                    );
+                }
+            }
+            BillAction::Wait {
+                id,
+                timeout,
+                interval,
+            } => {
+                let options = crate::WaitOptions::default()
+                    .timeout(*timeout)
+                    .interval(*interval);
+                let bill = client.wait_for_bill(id, options).await?;
+                output_json(&bill, cli.pretty);
+                if !bill.paid {
+                    return Err(format!("Bill {} was not paid ({})", bill.id, bill.state).into());
+                }
+            }
+            BillAction::Transactions {
+                id,
+                page,
+                all,
+                status,
+            } => {
+                let mut builder = client.get_bill_transactions(id);
+                if let Some(p) = page {
+                    builder = builder.page(*p);
+                }
+                if let Some(s) = status {
+                    builder = builder.status(s);
+                }
+                if *all {
+                    let result = builder.paginate().collect_all().await?;
+                    output_json(&result, cli.pretty);
+                } else {
+                    let result = builder.send().await?;
+                    output_json(&result, cli.pretty);
+                }
+            }
+        },
+        Commands::Card { action } => match action {
+            CardAction::Create {
+                name,
+                email,
+                phone,
+                expiry,
+            } => {
+                let (card_number, cvv) = read_card_secrets()?;
+                let result = client
+                    .create_card(name, email, phone, card_number, cvv, expiry)
+                    .send()
+                    .await?;
+                output_json(&result, cli.pretty);
+            }
+            CardAction::Delete { id, token } => {
+                let result = client.delete_card(id, token).await?;
+                output_json(&result, cli.pretty);
+            }
+            CardAction::Charge {
+                bill_id,
+                card_id,
+                token,
+            } => {
+                let result = client.charge_bill_with_token(bill_id, card_id, token).await?;
+                output_json(&result, cli.pretty);
+            }
         },
         Commands::Bank { action } => match action {
-            BankAction::FpxList => {
-                let banks = client.get_fpx_banks();
+            BankAction::FpxList { live, only_active } => {
+                let mut banks = if *live {
+                    client.fetch_fpx_banks().await?
+                } else {
+                    client.get_fpx_banks()
+                };
+                if *only_active {
+                    banks.retain(|b| b.active);
+                }
                 output_json(&banks, cli.pretty);
             }
             BankAction::Verify { account_number } => {
                 let result = client.get_bank_verification(account_number).await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
             }
             BankAction::CreateVerification {
                 name,
@@ -266,17 +881,13 @@ async fn execute_command(
                     .organization(*organization)
                     .send()
                     .await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
             }
         },
         Commands::Payout { action } => match action {
             PayoutAction::Get { id } => {
                 let result = client.get_payout(id).await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
             }
             PayoutAction::Create {
                 collection_id,
@@ -299,26 +910,145 @@ async fn execute_command(
                     )
                     .send()
                     .await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
+            }
+            PayoutAction::Import {
+                file,
+                collection_id,
+                concurrency,
+                require_verified,
+                results,
+                resume,
+            } => {
+                let rows: Vec<import::PayoutRow> = import::read_rows(file)?;
+                let import = import::PayoutImport {
+                    collection_id: collection_id.clone(),
+                    concurrency: *concurrency,
+                    require_verified: *require_verified,
+                    results: results
+                        .clone()
+                        .unwrap_or_else(|| import::default_results_path(file)),
+                    resume: *resume,
+                };
+                let summary = import.run(client, rows).await?;
+                output_json(&summary, cli.pretty);
+                if !summary.needs_review.is_empty() {
+                    eprintln!(
+                        "warning: rows {:?} got no answer from Billplz and may have been paid out. \
+                         Check them on Billplz; --resume will not send them again.",
+                        summary.needs_review
+                    );
+                }
             }
         },
         Commands::PayoutCollection { action } => match action {
             PayoutCollectionAction::Get { id } => {
                 let result = client.get_payout_collection(id).await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
             }
             PayoutCollectionAction::Create { title } => {
                 let result = client.create_payout_collection(title).send().await?;
-                let value: serde_json::Value = serde_json::from_str(&result)
-                    .unwrap_or(serde_json::Value::String(result));
-                output_json(&value, cli.pretty);
+                output_json(&result, cli.pretty);
+            }
+            PayoutCollectionAction::List { page, all } => {
+                let mut builder = client.list_payout_collections();
+                if let Some(p) = page {
+                    builder = builder.page(*p);
+                }
+                if *all {
+                    let result = builder.paginate().collect_all().await?;
+                    output_json(&result, cli.pretty);
+                } else {
+                    let result = builder.send().await?;
+                    output_json(&result, cli.pretty);
+                }
             }
         },
-        Commands::Mcp => unreachable!(),
+        Commands::PaymentOrder { action } => match action {
+            PaymentOrderAction::Get { id } => {
+                let result = client.get_payment_order(id).await?;
+                output_json(&result, cli.pretty);
+            }
+            PaymentOrderAction::Create {
+                collection_id,
+                bank_code,
+                acc_no,
+                name,
+                description,
+                total,
+                email,
+                recipient_notification,
+                reference_id,
+            } => {
+                let mut builder = client.create_payment_order(
+                    collection_id,
+                    bank_code,
+                    acc_no,
+                    name,
+                    description,
+                    *total,
+                );
+                if let Some(e) = email {
+                    builder = builder.email(e);
+                }
+                if *recipient_notification {
+                    builder = builder.recipient_notification(true);
+                }
+                if let Some(r) = reference_id {
+                    builder = builder.reference_id(r);
+                }
+                let result = builder.send().await?;
+                output_json(&result, cli.pretty);
+            }
+            PaymentOrderAction::Limit => {
+                let result = client.get_payment_order_limit().await?;
+                output_json(&result, cli.pretty);
+            }
+        },
+        Commands::PaymentOrderCollection { action } => match action {
+            PaymentOrderCollectionAction::Get { id } => {
+                let result = client.get_payment_order_collection(id).await?;
+                output_json(&result, cli.pretty);
+            }
+            PaymentOrderCollectionAction::Create { title, callback_url } => {
+                let mut builder = client.create_payment_order_collection(title);
+                if let Some(c) = callback_url {
+                    builder = builder.callback_url(c);
+                }
+                let result = builder.send().await?;
+                output_json(&result, cli.pretty);
+            }
+        },
+        Commands::Reconcile {
+            input,
+            concurrency,
+            csv,
+            output,
+        } => {
+            let ledger: Vec<crate::reconcile::LedgerEntry> = import::read_rows(input)?;
+            let report = client.reconcile(ledger, *concurrency).await;
+            let mut out: Box<dyn std::io::Write> = match output {
+                Some(path) => Box::new(std::fs::File::create(path)?),
+                None => Box::new(std::io::stdout()),
+            };
+            if *csv {
+                let mut writer = ::csv::Writer::from_writer(out);
+                for row in &report.rows {
+                    writer.serialize(row)?;
+                }
+                writer.flush()?;
+            } else {
+                if cli.pretty {
+                    serde_json::to_writer_pretty(&mut out, &report)?;
+                } else {
+                    serde_json::to_writer(&mut out, &report)?;
+                }
+                writeln!(out)?;
+            }
+        }
+        Commands::Mcp | Commands::Webhook { .. } => unreachable!(),
+        #[cfg(feature = "sim")]
+        Commands::Simulate { .. } => unreachable!(),
     }
     Ok(())
 }
@@ -0,0 +1,158 @@
+//! `billplz webhook listen`: a local receiver for Billplz callbacks.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use crate::models::bill::CallbackPayload;
+use crate::signature::rejection_status;
+use crate::{BillplzError, XSignature};
+
+/// How verified callbacks are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Indented JSON, one block per callback.
+    Pretty,
+    /// One JSON object per line, for piping into `jq` or log files.
+    JsonLines,
+}
+
+/// Verifies and prints incoming callbacks, optionally forwarding them.
+pub struct WebhookListener {
+    signer: XSignature,
+    forward_to: Option<String>,
+    format: OutputFormat,
+    bind_address: IpAddr,
+    http: reqwest::Client,
+}
+
+impl WebhookListener {
+    pub fn new(x_signature_key: impl Into<String>) -> Self {
+        Self {
+            signer: XSignature::new(x_signature_key),
+            forward_to: None,
+            format: OutputFormat::Pretty,
+            bind_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Re-POSTs every verified callback, unchanged, to this URL.
+    pub fn forward_to(mut self, url: impl Into<String>) -> Self {
+        self.forward_to = Some(url.into());
+        self
+    }
+
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Address `listen` binds to. Defaults to `127.0.0.1`; use `0.0.0.0` to
+    /// accept callbacks from other machines, e.g. through a tunnel.
+    pub fn bind_address(mut self, address: IpAddr) -> Self {
+        self.bind_address = address;
+        self
+    }
+
+    /// Binds `bind_address:port` and serves until the process is stopped.
+    pub async fn listen(self, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind(SocketAddr::new(self.bind_address, port)).await?;
+        eprintln!(
+            "Listening for Billplz callbacks on http://{}",
+            listener.local_addr()?
+        );
+        self.serve(listener).await
+    }
+
+    /// Serves callbacks on an already bound listener.
+    pub async fn serve(self, listener: TcpListener) -> Result<(), Box<dyn std::error::Error>> {
+        let listener_state = Arc::new(self);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let state = listener_state.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req| {
+                    let state = state.clone();
+                    async move { Ok::<_, hyper::Error>(state.handle(req).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    eprintln!("connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        if req.method() != Method::POST {
+            return respond(StatusCode::METHOD_NOT_ALLOWED, "expected a POST callback");
+        }
+        let body = match req.into_body().collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => return respond(StatusCode::BAD_REQUEST, &e.to_string()),
+        };
+        let payload = match std::str::from_utf8(&body)
+            .map_err(|e| BillplzError::InvalidPayload(e.to_string()))
+            .and_then(|form| self.signer.verify_callback_form(form))
+        {
+            Ok(payload) => payload,
+            Err(e) => {
+                eprintln!("rejected callback: {}", e);
+                let status = StatusCode::from_u16(rejection_status(&e))
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                return respond(status, &e.to_string());
+            }
+        };
+        self.print(&payload);
+
+        if let Some(url) = &self.forward_to {
+            let forwarded = self
+                .http
+                .post(url)
+                .header(
+                    reqwest::header::CONTENT_TYPE,
+                    "application/x-www-form-urlencoded",
+                )
+                .body(body.to_vec())
+                .send()
+                .await;
+            match forwarded {
+                Ok(resp) if resp.status().is_success() => {}
+                Ok(resp) => eprintln!("forward to {} returned {}", url, resp.status()),
+                Err(e) => {
+                    eprintln!("forward to {} failed: {}", url, e);
+                    return respond(StatusCode::BAD_GATEWAY, &e.to_string());
+                }
+            }
+        }
+
+        respond(StatusCode::OK, "OK")
+    }
+
+    fn print(&self, payload: &CallbackPayload) {
+        let printed = match self.format {
+            OutputFormat::Pretty => serde_json::to_string_pretty(payload),
+            OutputFormat::JsonLines => serde_json::to_string(payload),
+        };
+        match printed {
+            Ok(s) => println!("{}", s),
+            Err(e) => eprintln!("could not print callback: {}", e),
+        }
+    }
+}
+
+fn respond(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let mut resp = Response::new(Full::new(Bytes::from(message.to_string())));
+    *resp.status_mut() = status;
+    resp
+}
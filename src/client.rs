@@ -1,5 +1,16 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::de::DeserializeOwned;
 
+use crate::api::bank::{FpxBankCache, DEFAULT_FPX_BANK_CACHE_TTL};
+use crate::error::{BillplzError, ErrorContext};
+use crate::idempotency::{IdempotencyStore, KeyLocks, MemoryIdempotencyStore};
+use crate::retry::{self, RetryPolicy};
+use crate::signature::XSignature;
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+
 #[derive(Debug, Clone)]
 pub enum Environment {
     Production,
@@ -7,68 +18,447 @@ pub enum Environment {
 }
 
 pub struct BillplzClient {
+    /// Used to build requests; `transport` sends them.
     pub(crate) http: reqwest::Client,
+    pub(crate) transport: Arc<dyn Transport>,
     pub(crate) base_url: String,
     pub(crate) api_key: String,
     pub(crate) environment: Option<Environment>,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) x_signature: Option<XSignature>,
+    pub(crate) fpx_bank_cache: FpxBankCache,
+    pub(crate) idempotency_store: Arc<dyn IdempotencyStore>,
+    pub(crate) idempotency_locks: KeyLocks,
 }
 
-impl BillplzClient {
-    pub fn new(environment: Environment, api_key: impl Into<String>) -> Self {
-        let base_url = match &environment {
-            Environment::Production => "https://www.billplz.com".to_string(),
-            Environment::Staging => "https://www.billplz-sandbox.com".to_string(),
+impl Environment {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Environment::Production => "https://www.billplz.com",
+            Environment::Staging => "https://www.billplz-sandbox.com",
+        }
+    }
+}
+
+const DEFAULT_USER_AGENT: &str = concat!("billplz-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Configures a `BillplzClient`. Created with `BillplzClient::builder()`.
+#[derive(Default)]
+pub struct BillplzClientBuilder {
+    environment: Option<Environment>,
+    base_url: Option<String>,
+    api_key: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    headers: Vec<(String, String)>,
+    http_client: Option<reqwest::Client>,
+    transport: Option<Arc<dyn Transport>>,
+    retry_policy: Option<RetryPolicy>,
+    x_signature_key: Option<String>,
+    fpx_bank_cache_ttl: Option<Duration>,
+    idempotency_store: Option<Arc<dyn IdempotencyStore>>,
+    /// Set by `BillplzClient::with_base_url`, which cannot report a bad URL.
+    skip_base_url_check: bool,
+}
+
+impl BillplzClientBuilder {
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Overrides the environment's base URL, e.g. for a mock server.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Total timeout for each request attempt.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Routes all requests through the given proxy URL.
+    pub fn proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Uses an existing `reqwest::Client`, e.g. to share a connection pool.
+    /// Cannot be combined with timeout, proxy, user agent or header options.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sends requests through a custom transport instead of reqwest.
+    /// Cannot be combined with `http_client` or timeout, proxy, user agent or header options.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// X-Signature key from the Billplz account settings.
+    /// Required for the v5 Payment Order API, which signs every request with a checksum.
+    pub fn x_signature_key(mut self, key: impl Into<String>) -> Self {
+        self.x_signature_key = Some(key.into());
+        self
+    }
+
+    /// How long `fetch_fpx_banks` reuses a fetched list. Defaults to 15 minutes.
+    pub fn fpx_bank_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.fpx_bank_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Where bills created with an idempotency key are recorded.
+    /// Defaults to an in-memory store that lives as long as the client.
+    pub fn idempotency_store(mut self, store: impl IdempotencyStore + 'static) -> Self {
+        self.idempotency_store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn build(self) -> Result<BillplzClient, BillplzError> {
+        let api_key = self
+            .api_key
+            .ok_or_else(|| BillplzError::Config("API key is required".into()))?;
+
+        let base_url = match (&self.base_url, &self.environment) {
+            (Some(url), _) => url.clone(),
+            (None, Some(env)) => env.base_url().to_string(),
+            (None, None) => {
+                return Err(BillplzError::Config(
+                    "either an environment or a base URL is required".into(),
+                ))
+            }
         };
+        if !self.skip_base_url_check {
+            let parsed = reqwest::Url::parse(&base_url).map_err(|e| {
+                BillplzError::Config(format!("invalid base URL {:?}: {}", base_url, e))
+            })?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(BillplzError::Config(format!(
+                    "base URL must use http or https: {:?}",
+                    base_url
+                )));
+            }
+        }
+        let base_url = base_url.trim_end_matches('/').to_string();
 
-        Self {
-            http: reqwest::Client::new(),
-            base_url,
-            api_key: api_key.into(),
-            environment: Some(environment),
+        let customised = self.connect_timeout.is_some()
+            || self.timeout.is_some()
+            || self.proxy.is_some()
+            || self.user_agent.is_some()
+            || !self.headers.is_empty();
+
+        if self.transport.is_some() && (customised || self.http_client.is_some()) {
+            return Err(BillplzError::Config(
+                "transport cannot be combined with http_client or HTTP options".into(),
+            ));
         }
+
+        let http = match self.http_client {
+            Some(_) if customised => {
+                return Err(BillplzError::Config(
+                    "http_client cannot be combined with timeout, proxy, user agent or headers"
+                        .into(),
+                ))
+            }
+            Some(client) => client,
+            None => {
+                let mut headers = HeaderMap::new();
+                for (name, value) in &self.headers {
+                    let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                        BillplzError::Config(format!("invalid header name {:?}: {}", name, e))
+                    })?;
+                    let value = HeaderValue::from_str(value).map_err(|e| {
+                        BillplzError::Config(format!("invalid header value {:?}: {}", value, e))
+                    })?;
+                    headers.insert(name, value);
+                }
+
+                let mut builder = reqwest::Client::builder()
+                    .user_agent(self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+                    .default_headers(headers);
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = &self.proxy {
+                    let proxy = reqwest::Proxy::all(proxy).map_err(|e| {
+                        BillplzError::Config(format!("invalid proxy {:?}: {}", proxy, e))
+                    })?;
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new(http.clone())),
+        };
+
+        Ok(BillplzClient {
+            http,
+            transport,
+            base_url,
+            api_key,
+            environment: self.environment,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            x_signature: self.x_signature_key.map(XSignature::new),
+            fpx_bank_cache: FpxBankCache::new(
+                self.fpx_bank_cache_ttl.unwrap_or(DEFAULT_FPX_BANK_CACHE_TTL),
+            ),
+            idempotency_store: self
+                .idempotency_store
+                .unwrap_or_else(|| Arc::new(MemoryIdempotencyStore::new())),
+            idempotency_locks: KeyLocks::default(),
+        })
+    }
+}
+
+impl BillplzClient {
+    pub fn builder() -> BillplzClientBuilder {
+        BillplzClientBuilder::default()
+    }
+
+    pub fn new(environment: Environment, api_key: impl Into<String>) -> Self {
+        Self::builder()
+            .environment(environment)
+            .api_key(api_key)
+            .build()
+            .expect("default client configuration is valid")
     }
 
     /// Create a client with a custom base URL (useful for testing with wiremock).
+    ///
+    /// The URL is not checked here, so a bad one only fails each request with
+    /// `BillplzError::Http`. Use `builder().base_url(..).build()` to reject it up front.
     pub fn with_base_url(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
-        Self {
-            http: reqwest::Client::new(),
-            base_url: base_url.into(),
-            api_key: api_key.into(),
-            environment: None,
-        }
+        let builder = BillplzClientBuilder {
+            skip_base_url_check: true,
+            ..Self::builder()
+        };
+        builder
+            .base_url(base_url)
+            .api_key(api_key)
+            .build()
+            .expect("default client configuration is valid")
+    }
+
+    /// Replace the retry policy used for every request.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the X-Signature key used to sign v5 Payment Order requests.
+    pub fn with_x_signature_key(mut self, key: impl Into<String>) -> Self {
+        self.x_signature = Some(XSignature::new(key));
+        self
+    }
+
+    /// Replace the store used to deduplicate bills created with an idempotency key.
+    pub fn with_idempotency_store(mut self, store: impl IdempotencyStore + 'static) -> Self {
+        self.idempotency_store = Arc::new(store);
+        self
     }
 
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
-    pub(crate) async fn parse_response<T: DeserializeOwned>(
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    pub(crate) fn x_signature(&self) -> Result<&XSignature, BillplzError> {
+        self.x_signature.as_ref().ok_or_else(|| {
+            BillplzError::Config("an X-Signature key is required for payment orders".into())
+        })
+    }
+
+    /// Sends a request through the transport, retrying transient failures
+    /// according to the retry policy.
+    pub(crate) async fn execute(
         &self,
-        resp: reqwest::Response,
-    ) -> Result<T, crate::error::BillplzError> {
-        let status = resp.status();
-        let body = resp.text().await?;
-
-        if !status.is_success() {
-            #[derive(serde::Deserialize)]
-            struct ApiErrorWrapper {
-                error: ApiErrorDetail,
-            }
-            #[derive(serde::Deserialize)]
-            struct ApiErrorDetail {
-                r#type: String,
-                message: String,
-            }
+        request: reqwest::RequestBuilder,
+    ) -> Result<Exchange, BillplzError> {
+        self.execute_with(request, false).await
+    }
+
+    /// Like `execute`, but a POST is retried too. Only for requests whose
+    /// idempotency key has been claimed with `IdempotencyStore::begin`.
+    pub(crate) async fn execute_claimed(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<Exchange, BillplzError> {
+        self.execute_with(request, true).await
+    }
 
-            if let Ok(api_err) = serde_json::from_str::<ApiErrorWrapper>(&body) {
-                return Err(crate::error::BillplzError::Api {
-                    error_type: api_err.error.r#type,
-                    message: api_err.error.message,
-                });
+    async fn execute_with(
+        &self,
+        request: reqwest::RequestBuilder,
+        claimed: bool,
+    ) -> Result<Exchange, BillplzError> {
+        let request = request.build()?;
+        let body = match request.body() {
+            None => None,
+            Some(body) => Some(
+                body.as_bytes()
+                    .ok_or_else(|| BillplzError::Config("streaming bodies are not supported".into()))?
+                    .to_vec(),
+            ),
+        };
+        let request = HttpRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body,
+        };
+
+        let method = request.method.to_string();
+        let path = request.url.path().to_string();
+        let exchange = |response| Exchange {
+            method: method.clone(),
+            path: path.clone(),
+            response,
+        };
+
+        let policy = &self.retry_policy;
+        let retryable = policy.allows(&request, claimed);
+        let mut attempt = 1;
+
+        loop {
+            if !retryable || attempt >= policy.max_attempts {
+                return self.transport.send(request).await.map(exchange);
             }
+
+            let delay = match self.transport.send(request.clone()).await {
+                Ok(resp) if policy.should_retry_status(resp.status) => {
+                    policy.delay(attempt, retry::retry_after(&resp))
+                }
+                Ok(resp) => return Ok(exchange(resp)),
+                Err(e) if policy.should_retry_error(&request, &e) => policy.delay(attempt, None),
+                Err(e) => return Err(e),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    pub(crate) async fn parse_response<T: DeserializeOwned>(
+        &self,
+        exchange: Exchange,
+    ) -> Result<T, BillplzError> {
+        let resp = exchange.response;
+        let body = resp.text();
+
+        if !resp.status.is_success() {
+            let context =
+                ErrorContext::new(resp.status.as_u16(), exchange.method, exchange.path, &body);
+            return Err(api_error(&resp, Box::new(context)));
         }
 
         let parsed: T = serde_json::from_str(&body)?;
         Ok(parsed)
     }
 }
+
+/// A response together with the request it answers, for error reporting.
+pub(crate) struct Exchange {
+    method: String,
+    path: String,
+    response: HttpResponse,
+}
+
+/// Classifies a non-2xx response. Billplz error bodies look like
+/// `{"error": {"type": "...", "message": "..." | ["...", ...]}}`.
+fn api_error(resp: &HttpResponse, context: Box<ErrorContext>) -> BillplzError {
+    #[derive(serde::Deserialize)]
+    struct ApiErrorWrapper {
+        error: ApiErrorDetail,
+    }
+    #[derive(serde::Deserialize)]
+    struct ApiErrorDetail {
+        r#type: String,
+        message: ApiErrorMessage,
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum ApiErrorMessage {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    let detail = serde_json::from_slice::<ApiErrorWrapper>(&resp.body)
+        .ok()
+        .map(|w| w.error);
+    let messages = match detail.as_ref().map(|d| &d.message) {
+        Some(ApiErrorMessage::One(m)) => vec![m.clone()],
+        Some(ApiErrorMessage::Many(m)) => m.clone(),
+        None => Vec::new(),
+    };
+    let message = if messages.is_empty() {
+        resp.status
+            .canonical_reason()
+            .unwrap_or("Unknown error")
+            .to_string()
+    } else {
+        messages.join("; ")
+    };
+    let is_list = matches!(
+        detail.as_ref().map(|d| &d.message),
+        Some(ApiErrorMessage::Many(_))
+    );
+
+    match resp.status.as_u16() {
+        401 => BillplzError::Unauthorized { message, context },
+        404 => BillplzError::NotFound { message, context },
+        429 => BillplzError::RateLimited {
+            retry_after: retry::retry_after(resp),
+            context,
+        },
+        422 if !messages.is_empty() => BillplzError::Validation { messages, context },
+        _ if is_list => BillplzError::Validation { messages, context },
+        _ => BillplzError::Api {
+            error_type: detail
+                .map(|d| d.r#type)
+                .unwrap_or_else(|| format!("http_{}", context.status)),
+            message,
+            context,
+        },
+    }
+}
@@ -1,3 +1,37 @@
+use std::time::Duration;
+
+/// Raw bodies longer than this are truncated in error values.
+pub const MAX_ERROR_BODY_LEN: usize = 1024;
+
+/// Where an API error came from: the HTTP status, request and (truncated) raw body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub status: u16,
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl ErrorContext {
+    pub fn new(
+        status: u16,
+        method: impl Into<String>,
+        path: impl Into<String>,
+        body: &str,
+    ) -> Self {
+        let body = match body.char_indices().nth(MAX_ERROR_BODY_LEN) {
+            Some((end, _)) => format!("{}...", &body[..end]),
+            None => body.to_string(),
+        };
+        Self {
+            status,
+            method: method.into(),
+            path: path.into(),
+            body,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BillplzError {
     #[error("HTTP error: {0}")]
@@ -7,8 +41,112 @@ pub enum BillplzError {
     Api {
         error_type: String,
         message: String,
+        context: Box<ErrorContext>,
+    },
+
+    #[error("Unauthorized: {message}")]
+    Unauthorized {
+        message: String,
+        context: Box<ErrorContext>,
+    },
+
+    #[error("Not found: {message}")]
+    NotFound {
+        message: String,
+        context: Box<ErrorContext>,
+    },
+
+    #[error(
+        "Rate limited{}",
+        retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default()
+    )]
+    RateLimited {
+        retry_after: Option<Duration>,
+        context: Box<ErrorContext>,
+    },
+
+    #[error("Validation failed: {}", messages.join("; "))]
+    Validation {
+        messages: Vec<String>,
+        context: Box<ErrorContext>,
     },
 
     #[error("JSON parse error: {0}")]
     Parse(#[from] serde_json::Error),
+
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    #[error("X-Signature verification failed")]
+    InvalidSignature,
+
+    #[error("Invalid payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Idempotency store error: {0}")]
+    Store(String),
+
+    #[error(
+        "Outcome unknown for idempotency key {key}: an earlier send got no answer, so the bill may exist. \
+         Check Billplz, then release the key from the store if no bill was created"
+    )]
+    OutcomeUnknown { key: String },
+
+    #[error("Bank account {account_number} is not verified ({status})")]
+    UnverifiedAccount {
+        account_number: String,
+        status: String,
+    },
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(#[from] crate::validation::ValidationError),
+
+    #[error("Timed out waiting for bill {bill_id} (still {state})")]
+    WaitTimeout { bill_id: String, state: String },
+}
+
+impl BillplzError {
+    /// Status, request and raw body for errors returned by the Billplz API.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            BillplzError::Api { context, .. }
+            | BillplzError::Unauthorized { context, .. }
+            | BillplzError::NotFound { context, .. }
+            | BillplzError::RateLimited { context, .. }
+            | BillplzError::Validation { context, .. } => Some(context.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code, if the error came from an API response.
+    pub fn status(&self) -> Option<u16> {
+        self.context().map(|c| c.status)
+    }
+
+    /// Whether repeating the same request may succeed: timeouts, connection
+    /// failures, rate limiting and 5xx responses.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BillplzError::Http(e) => e.is_timeout() || e.is_connect(),
+            BillplzError::RateLimited { .. } => true,
+            BillplzError::Api { context, .. } => context.status >= 500,
+            _ => false,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, BillplzError::NotFound { .. })
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, BillplzError::Unauthorized { .. })
+    }
+
+    /// How long the server asked us to wait, for rate-limited requests.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BillplzError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
 }
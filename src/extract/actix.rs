@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::web::{Bytes, Data};
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+use super::{Rejection, VerifiedCallback, VerifiedRedirect};
+use crate::error::BillplzError;
+use crate::XSignature;
+
+/// Looks up the key registered with `App::app_data(XSignature)` or `App::app_data(Data::new(XSignature))`.
+fn signer(req: &HttpRequest) -> Result<XSignature, Rejection> {
+    req.app_data::<XSignature>()
+        .cloned()
+        .or_else(|| {
+            req.app_data::<Data<XSignature>>()
+                .map(|data| data.get_ref().clone())
+        })
+        .ok_or_else(|| {
+            Rejection(BillplzError::Config(
+                "an XSignature must be registered as app data".into(),
+            ))
+        })
+}
+
+impl FromRequest for VerifiedCallback {
+    type Error = Rejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let signer = signer(req);
+        let body = Bytes::from_request(req, payload);
+        Box::pin(async move {
+            let signer = signer?;
+            let body = body
+                .await
+                .map_err(|e| BillplzError::InvalidPayload(e.to_string()))?;
+            let body = std::str::from_utf8(&body)
+                .map_err(|e| BillplzError::InvalidPayload(e.to_string()))?;
+            Ok(Self(signer.verify_callback_form(body)?))
+        })
+    }
+}
+
+impl FromRequest for VerifiedRedirect {
+    type Error = Rejection;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        std::future::ready(
+            signer(req).and_then(|signer| Ok(Self(signer.verify_redirect_query(req.query_string())?))),
+        )
+    }
+}
+
+impl ResponseError for Rejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).body(self.0.to_string())
+    }
+}
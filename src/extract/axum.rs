@@ -0,0 +1,49 @@
+use axum::body::Bytes;
+use axum::extract::{FromRef, FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use super::{Rejection, VerifiedCallback, VerifiedRedirect};
+use crate::error::BillplzError;
+use crate::XSignature;
+
+impl<S> FromRequest<S> for VerifiedCallback
+where
+    XSignature: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let signer = XSignature::from_ref(state);
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| BillplzError::InvalidPayload(e.body_text()))?;
+        let body = std::str::from_utf8(&body)
+            .map_err(|e| BillplzError::InvalidPayload(e.to_string()))?;
+        Ok(Self(signer.verify_callback_form(body)?))
+    }
+}
+
+impl<S> FromRequestParts<S> for VerifiedRedirect
+where
+    XSignature: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let signer = XSignature::from_ref(state);
+        let query = parts.uri.query().unwrap_or_default();
+        Ok(Self(signer.verify_redirect_query(query)?))
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, self.0.to_string()).into_response()
+    }
+}
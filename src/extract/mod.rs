@@ -0,0 +1,53 @@
+//! Web framework extractors that verify Billplz callbacks and redirects.
+//!
+//! Enable the `axum` or `actix` feature. Handlers take [`VerifiedCallback`] or
+//! [`VerifiedRedirect`] and only run when the X-Signature checks out; the key
+//! is read from application state as an [`XSignature`](crate::XSignature).
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+
+use crate::error::BillplzError;
+use crate::models::bill::{CallbackPayload, RedirectPayload};
+
+/// A callback POST whose X-Signature has been verified.
+#[derive(Debug, Clone)]
+pub struct VerifiedCallback(pub CallbackPayload);
+
+/// A redirect query string whose X-Signature has been verified.
+#[derive(Debug, Clone)]
+pub struct VerifiedRedirect(pub RedirectPayload);
+
+/// Why an extractor refused a request.
+///
+/// Responds `401` for a missing or bad signature, `400` for a body that
+/// cannot be decoded and `500` when no X-Signature key is registered in
+/// application state.
+#[derive(Debug)]
+pub struct Rejection(pub BillplzError);
+
+impl Rejection {
+    pub fn status(&self) -> u16 {
+        crate::signature::rejection_status(&self.0)
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Rejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<BillplzError> for Rejection {
+    fn from(err: BillplzError) -> Self {
+        Self(err)
+    }
+}
@@ -0,0 +1,336 @@
+//! Local deduplication for bill creation.
+//!
+//! Billplz has no server-side idempotency for `POST /api/v3/bills`. When
+//! `CreateBillBuilder::idempotency_key` is set, the key is claimed in the
+//! client's `IdempotencyStore` as pending before the bill is posted, and the
+//! created bill is recorded once Billplz answers. A later send with the same
+//! key returns the recorded bill instead of creating another one.
+//!
+//! If the earlier send never got an answer (a timeout, a dropped connection
+//! or a 5xx), the bill may or may not exist, so the key stays pending and
+//! later sends fail with `BillplzError::OutcomeUnknown` rather than risk a
+//! second bill. Check Billplz for a bill carrying the key in its reference
+//! field, then `release` the key if none was created.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::BillplzError;
+use crate::models::bill::BillResponse;
+
+/// Bill field that carries the idempotency key, so it is visible on the bill itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdempotencyField {
+    Reference1,
+    #[default]
+    Reference2,
+}
+
+/// What a store knows about one idempotency key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdempotencyRecord {
+    /// A send was started and has not been confirmed either way.
+    Pending,
+    /// The bill created under the key.
+    Completed(Box<BillResponse>),
+}
+
+/// Remembers which bill was created for each idempotency key.
+pub trait IdempotencyStore: Send + Sync {
+    /// The record for `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError>;
+
+    /// Atomically claims `key` before a send: returns the existing record if
+    /// there is one, otherwise records `key` as pending and returns `None`.
+    fn begin(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError>;
+
+    /// Records `bill` as the result for `key`.
+    fn complete(&self, key: &str, bill: &BillResponse) -> Result<(), BillplzError>;
+
+    /// Forgets `key`. Called when Billplz rejected the send, and by you once
+    /// you have confirmed a pending key created no bill.
+    fn release(&self, key: &str) -> Result<(), BillplzError>;
+}
+
+/// Keeps keys for the lifetime of the process. The default store.
+#[derive(Debug, Default)]
+pub struct MemoryIdempotencyStore {
+    records: Mutex<HashMap<String, IdempotencyRecord>>,
+}
+
+impl MemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdempotencyStore for MemoryIdempotencyStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        Ok(self.records.lock().unwrap().get(key).cloned())
+    }
+
+    fn begin(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.get(key) {
+            return Ok(Some(record.clone()));
+        }
+        records.insert(key.to_string(), IdempotencyRecord::Pending);
+        Ok(None)
+    }
+
+    fn complete(&self, key: &str, bill: &BillResponse) -> Result<(), BillplzError> {
+        self.records.lock().unwrap().insert(
+            key.to_string(),
+            IdempotencyRecord::Completed(Box::new(bill.clone())),
+        );
+        Ok(())
+    }
+
+    fn release(&self, key: &str) -> Result<(), BillplzError> {
+        self.records.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Persists keys as a JSON object in a single file, so they survive restarts.
+///
+/// The file is rewritten atomically on every change. Claims are only atomic
+/// within one process; use the `sqlite` feature's `SqliteIdempotencyStore`
+/// when several processes share keys.
+#[derive(Debug)]
+pub struct FileIdempotencyStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+type FileRecords = HashMap<String, IdempotencyRecord>;
+
+impl FileIdempotencyStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn load(&self) -> Result<FileRecords, BillplzError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(s) if s.trim().is_empty() => Ok(HashMap::new()),
+            Ok(s) => Ok(serde_json::from_str(&s)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(store_error(&self.path, e)),
+        }
+    }
+
+    fn save(&self, records: &FileRecords) -> Result<(), BillplzError> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| store_error(dir, e))?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(records)?)
+            .map_err(|e| store_error(&tmp, e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| store_error(&self.path, e))
+    }
+
+    fn update(&self, change: impl FnOnce(&mut FileRecords)) -> Result<(), BillplzError> {
+        let _guard = self.lock.lock().unwrap();
+        let mut records = self.load()?;
+        change(&mut records);
+        self.save(&records)
+    }
+}
+
+impl IdempotencyStore for FileIdempotencyStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self.load()?.remove(key))
+    }
+
+    fn begin(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        let _guard = self.lock.lock().unwrap();
+        let mut records = self.load()?;
+        if let Some(record) = records.get(key) {
+            return Ok(Some(record.clone()));
+        }
+        records.insert(key.to_string(), IdempotencyRecord::Pending);
+        self.save(&records)?;
+        Ok(None)
+    }
+
+    fn complete(&self, key: &str, bill: &BillResponse) -> Result<(), BillplzError> {
+        self.update(|records| {
+            records.insert(
+                key.to_string(),
+                IdempotencyRecord::Completed(Box::new(bill.clone())),
+            );
+        })
+    }
+
+    fn release(&self, key: &str) -> Result<(), BillplzError> {
+        self.update(|records| {
+            records.remove(key);
+        })
+    }
+}
+
+fn store_error(path: &Path, err: impl std::fmt::Display) -> BillplzError {
+    BillplzError::Store(format!("{}: {}", path.display(), err))
+}
+
+/// Serialises sends that share an idempotency key within one client, so a
+/// concurrent duplicate waits for the first send's outcome instead of
+/// finding its key pending.
+#[derive(Debug, Default)]
+pub(crate) struct KeyLocks {
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+pub(crate) struct KeyGuard<'a> {
+    locks: &'a KeyLocks,
+    key: String,
+    _guard: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+impl KeyLocks {
+    pub(crate) async fn lock(&self, key: &str) -> KeyGuard<'_> {
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        KeyGuard {
+            locks: self,
+            key: key.to_string(),
+            _guard: Some(lock.lock_owned().await),
+        }
+    }
+}
+
+impl Drop for KeyGuard<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.locks.lock().unwrap();
+        self._guard = None;
+        // Only the map's own reference left: nobody else is waiting.
+        if locks
+            .get(&self.key)
+            .is_some_and(|l| Arc::strong_count(l) == 1)
+        {
+            locks.remove(&self.key);
+        }
+    }
+}
+
+/// Persists keys in a SQLite table, `billplz_idempotency_keys`.
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+pub struct SqliteIdempotencyStore {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteIdempotencyStore {
+    /// Opens (or creates) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BillplzError> {
+        let path = path.as_ref();
+        let conn = rusqlite::Connection::open(path).map_err(|e| store_error(path, e))?;
+        Self::with_connection(conn)
+    }
+
+    /// Uses an existing connection, creating the table if needed. A `NULL`
+    /// bill marks a pending key.
+    pub fn with_connection(conn: rusqlite::Connection) -> Result<Self, BillplzError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS billplz_idempotency_keys (
+                key TEXT PRIMARY KEY,
+                bill TEXT
+            )",
+            [],
+        )
+        .map_err(sqlite_error)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn read(
+        conn: &rusqlite::Connection,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        use rusqlite::OptionalExtension;
+
+        let bill: Option<Option<String>> = conn
+            .query_row(
+                "SELECT bill FROM billplz_idempotency_keys WHERE key = ?1",
+                [key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        match bill {
+            None => Ok(None),
+            Some(None) => Ok(Some(IdempotencyRecord::Pending)),
+            Some(Some(bill)) => Ok(Some(IdempotencyRecord::Completed(Box::new(
+                serde_json::from_str(&bill)?,
+            )))),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl IdempotencyStore for SqliteIdempotencyStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        Self::read(&self.conn.lock().unwrap(), key)
+    }
+
+    fn begin(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO billplz_idempotency_keys (key, bill) VALUES (?1, NULL)",
+                [key],
+            )
+            .map_err(sqlite_error)?;
+        if inserted == 1 {
+            Ok(None)
+        } else {
+            Self::read(&conn, key)
+        }
+    }
+
+    fn complete(&self, key: &str, bill: &BillResponse) -> Result<(), BillplzError> {
+        let bill = serde_json::to_string(bill)?;
+        self.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT OR REPLACE INTO billplz_idempotency_keys (key, bill) VALUES (?1, ?2)",
+                [key, bill.as_str()],
+            )
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    fn release(&self, key: &str) -> Result<(), BillplzError> {
+        self.conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM billplz_idempotency_keys WHERE key = ?1", [key])
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_error(err: rusqlite::Error) -> BillplzError {
+    BillplzError::Store(err.to_string())
+}
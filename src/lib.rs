@@ -1,9 +1,34 @@
 pub mod cli;
 pub mod error;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod extract;
+pub mod idempotency;
 pub mod models;
 pub mod api;
 pub mod mcp;
+pub mod reconcile;
+pub mod signature;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod transport;
+pub mod validation;
 mod client;
+mod retry;
 
-pub use client::{BillplzClient, Environment};
-pub use error::BillplzError;
+pub use api::bulk::{BulkOutcome, BulkPayoutOptions};
+pub use api::paginator::Paginator;
+pub use api::wait::{BillEvent, BillWatch, WaitOptions, MIN_POLL_INTERVAL};
+pub use client::{BillplzClient, BillplzClientBuilder, Environment};
+pub use error::{BillplzError, ErrorContext};
+pub use idempotency::{
+    FileIdempotencyStore, IdempotencyField, IdempotencyRecord, IdempotencyStore,
+    MemoryIdempotencyStore,
+};
+#[cfg(feature = "sqlite")]
+pub use idempotency::SqliteIdempotencyStore;
+pub use retry::RetryPolicy;
+pub use signature::XSignature;
+#[cfg(feature = "sim")]
+pub use sim::Simulator;
+pub use transport::{ReqwestTransport, Transport};
+pub use validation::{FieldError, ValidationError};
@@ -38,6 +38,53 @@ pub struct CreateCollectionInput {
     pub split_header: Option<bool>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListCollectionsInput {
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Filter by status (active, inactive)
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdatePaymentMethodsInput {
+    /// The collection ID
+    pub id: String,
+    /// Payment method codes to enable (e.g. fpx, creditcard, boost)
+    pub codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateOpenCollectionInput {
+    /// Payment form title
+    pub title: String,
+    /// Payment form description
+    pub description: String,
+    /// Fixed amount in cents (omit when fixed_amount is false)
+    #[serde(default)]
+    pub amount: Option<i64>,
+    /// Set to false to let payers enter their own amount
+    #[serde(default)]
+    pub fixed_amount: Option<bool>,
+    /// Set to false to let payers choose a quantity
+    #[serde(default)]
+    pub fixed_quantity: Option<bool>,
+    /// Button label (pay, buy)
+    #[serde(default)]
+    pub payment_button: Option<String>,
+    /// Reference 1 label
+    #[serde(default)]
+    pub reference_1_label: Option<String>,
+    /// Reference 2 label
+    #[serde(default)]
+    pub reference_2_label: Option<String>,
+    /// Redirect URI after payment
+    #[serde(default)]
+    pub redirect_uri: Option<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct CreateBillInput {
     /// Collection ID to create the bill under
@@ -72,6 +119,52 @@ pub struct CreateBillInput {
     /// Reference 2 value
     #[serde(default)]
     pub reference_2: Option<String>,
+    /// FPX bank code to preselect (sets reference 1 to "Bank Code")
+    #[serde(default)]
+    pub preferred_bank: Option<String>,
+    /// Returns the bill already created with this key instead of creating a duplicate (stored in reference 2)
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetBillTransactionsInput {
+    /// The bill ID
+    pub id: String,
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// Filter by status (pending, completed, failed)
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteCardInput {
+    /// The card ID
+    pub id: String,
+    /// The card token returned when the card was created
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ChargeBillInput {
+    /// The bill ID to charge
+    pub bill_id: String,
+    /// The card ID
+    pub card_id: String,
+    /// The card token returned when the card was created
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetFpxBanksInput {
+    /// Fetch the current list from Billplz instead of the built-in table
+    #[serde(default)]
+    pub live: bool,
+    /// Only return banks currently accepting payments
+    #[serde(default)]
+    pub only_active: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -119,6 +212,47 @@ pub struct CreatePayoutCollectionInput {
     pub title: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListPayoutCollectionsInput {
+    /// Page number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreatePaymentOrderCollectionInput {
+    /// Payment order collection title
+    pub title: String,
+    /// URL notified when payment orders change status
+    #[serde(default)]
+    pub callback_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreatePaymentOrderInput {
+    /// Payment order collection ID
+    pub collection_id: String,
+    /// Bank SWIFT code
+    pub bank_code: String,
+    /// Recipient bank account number
+    pub acc_no: String,
+    /// Recipient name
+    pub name: String,
+    /// Payment description
+    pub description: String,
+    /// Total amount in cents
+    pub total: i64,
+    /// Recipient email
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Email the recipient when the payment completes
+    #[serde(default)]
+    pub recipient_notification: Option<bool>,
+    /// Your own reference for this payment
+    #[serde(default)]
+    pub reference_id: Option<String>,
+}
+
 // --- Tool implementations ---
 
 #[tool_router]
@@ -149,6 +283,162 @@ impl BillplzMcp {
         }
     }
 
+    #[tool(description = "List Billplz collections, optionally filtered by page and status")]
+    async fn list_collections(
+        &self,
+        Parameters(input): Parameters<ListCollectionsInput>,
+    ) -> String {
+        let mut builder = self.client.list_collections();
+        if let Some(p) = input.page {
+            builder = builder.page(p);
+        }
+        if let Some(s) = &input.status {
+            builder = builder.status(s);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Activate a Billplz collection")]
+    async fn activate_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.activate_collection(&input.id).await {
+            Ok(()) => format!("Collection {} activated", input.id),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Deactivate a Billplz collection")]
+    async fn deactivate_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.deactivate_collection(&input.id).await {
+            Ok(()) => format!("Collection {} deactivated", input.id),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List the payment methods (FPX, card, e-wallet, BNPL) on a collection and whether each is enabled")]
+    async fn get_payment_methods(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.get_payment_methods(&input.id).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Set which payment methods are enabled on a collection")]
+    async fn update_payment_methods(
+        &self,
+        Parameters(input): Parameters<UpdatePaymentMethodsInput>,
+    ) -> String {
+        match self.client.update_payment_methods(&input.id, input.codes).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List payment gateways (banks and channels) currently available to the account")]
+    async fn get_payment_gateways(&self) -> String {
+        match self.client.get_payment_gateways().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Get a Billplz open collection (payment form) by ID")]
+    async fn get_open_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.get_open_collection(&input.id).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Create a Billplz open collection (payment form). Amount is in cents.")]
+    async fn create_open_collection(
+        &self,
+        Parameters(input): Parameters<CreateOpenCollectionInput>,
+    ) -> String {
+        let mut builder = self
+            .client
+            .create_open_collection(&input.title, &input.description);
+        if let Some(a) = input.amount {
+            builder = builder.amount(a);
+        }
+        if let Some(f) = input.fixed_amount {
+            builder = builder.fixed_amount(f);
+        }
+        if let Some(f) = input.fixed_quantity {
+            builder = builder.fixed_quantity(f);
+        }
+        if let Some(b) = &input.payment_button {
+            builder = builder.payment_button(b);
+        }
+        if let Some(l) = &input.reference_1_label {
+            builder = builder.reference_1_label(l);
+        }
+        if let Some(l) = &input.reference_2_label {
+            builder = builder.reference_2_label(l);
+        }
+        if let Some(r) = &input.redirect_uri {
+            builder = builder.redirect_uri(r);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List Billplz open collections, optionally filtered by page and status")]
+    async fn list_open_collections(
+        &self,
+        Parameters(input): Parameters<ListCollectionsInput>,
+    ) -> String {
+        let mut builder = self.client.list_open_collections();
+        if let Some(p) = input.page {
+            builder = builder.page(p);
+        }
+        if let Some(s) = &input.status {
+            builder = builder.status(s);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Activate a Billplz open collection")]
+    async fn activate_open_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.activate_open_collection(&input.id).await {
+            Ok(()) => format!("Open collection {} activated", input.id),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Deactivate a Billplz open collection")]
+    async fn deactivate_open_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.deactivate_open_collection(&input.id).await {
+            Ok(()) => format!("Open collection {} deactivated", input.id),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
     #[tool(description = "Get a Billplz bill by ID")]
     async fn get_bill(
         &self,
@@ -192,15 +482,89 @@ impl BillplzMcp {
         if let Some(v) = &input.reference_2 {
             builder = builder.reference_2(v);
         }
+        if let Some(b) = &input.preferred_bank {
+            builder = builder.preferred_bank(b.as_str());
+        }
+        if let Some(k) = &input.idempotency_key {
+            builder = builder.idempotency_key(k);
+        }
         match builder.send().await {
             Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
 
-    #[tool(description = "List all Malaysian FPX banks available for online payment")]
-    async fn get_fpx_banks(&self) -> String {
-        let banks = self.client.get_fpx_banks();
+    #[tool(description = "Delete an unpaid Billplz bill by ID")]
+    async fn delete_bill(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.delete_bill(&input.id).await {
+            Ok(()) => format!("Bill {} deleted", input.id),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List payment transactions (attempts) for a Billplz bill")]
+    async fn get_bill_transactions(
+        &self,
+        Parameters(input): Parameters<GetBillTransactionsInput>,
+    ) -> String {
+        let mut builder = self.client.get_bill_transactions(&input.id);
+        if let Some(p) = input.page {
+            builder = builder.page(p);
+        }
+        if let Some(s) = &input.status {
+            builder = builder.status(s);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Delete a tokenized card")]
+    async fn delete_card(
+        &self,
+        Parameters(input): Parameters<DeleteCardInput>,
+    ) -> String {
+        match self.client.delete_card(&input.id, &input.token).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Charge an existing bill to a tokenized card")]
+    async fn charge_bill_with_token(
+        &self,
+        Parameters(input): Parameters<ChargeBillInput>,
+    ) -> String {
+        match self
+            .client
+            .charge_bill_with_token(&input.bill_id, &input.card_id, &input.token)
+            .await
+        {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List Malaysian FPX banks available for online payment, optionally live from Billplz with their active status")]
+    async fn get_fpx_banks(
+        &self,
+        Parameters(input): Parameters<GetFpxBanksInput>,
+    ) -> String {
+        let mut banks = if input.live {
+            match self.client.fetch_fpx_banks().await {
+                Ok(banks) => banks,
+                Err(e) => return format!("Error: {}", e),
+            }
+        } else {
+            self.client.get_fpx_banks()
+        };
+        if input.only_active {
+            banks.retain(|b| b.active);
+        }
         serde_json::to_string_pretty(&banks).unwrap_or_else(|e| e.to_string())
     }
 
@@ -210,7 +574,7 @@ impl BillplzMcp {
         Parameters(input): Parameters<GetBankVerificationInput>,
     ) -> String {
         match self.client.get_bank_verification(&input.account_number).await {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -226,7 +590,7 @@ impl BillplzMcp {
             .send()
             .await
         {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -237,7 +601,7 @@ impl BillplzMcp {
         Parameters(input): Parameters<GetByIdInput>,
     ) -> String {
         match self.client.get_payout(&input.id).await {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -260,7 +624,7 @@ impl BillplzMcp {
             .send()
             .await
         {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -271,7 +635,7 @@ impl BillplzMcp {
         Parameters(input): Parameters<GetByIdInput>,
     ) -> String {
         match self.client.get_payout_collection(&input.id).await {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -282,7 +646,95 @@ impl BillplzMcp {
         Parameters(input): Parameters<CreatePayoutCollectionInput>,
     ) -> String {
         match self.client.create_payout_collection(&input.title).send().await {
-            Ok(r) => r,
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "List payout collections, optionally by page")]
+    async fn list_payout_collections(
+        &self,
+        Parameters(input): Parameters<ListPayoutCollectionsInput>,
+    ) -> String {
+        let mut builder = self.client.list_payout_collections();
+        if let Some(p) = input.page {
+            builder = builder.page(p);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Get a v5 payment order collection by ID")]
+    async fn get_payment_order_collection(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.get_payment_order_collection(&input.id).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Create a v5 payment order collection")]
+    async fn create_payment_order_collection(
+        &self,
+        Parameters(input): Parameters<CreatePaymentOrderCollectionInput>,
+    ) -> String {
+        let mut builder = self.client.create_payment_order_collection(&input.title);
+        if let Some(c) = &input.callback_url {
+            builder = builder.callback_url(c);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Get a v5 payment order by ID")]
+    async fn get_payment_order(
+        &self,
+        Parameters(input): Parameters<GetByIdInput>,
+    ) -> String {
+        match self.client.get_payment_order(&input.id).await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Create a v5 payment order (payout to a bank account). Total is in cents.")]
+    async fn create_payment_order(
+        &self,
+        Parameters(input): Parameters<CreatePaymentOrderInput>,
+    ) -> String {
+        let mut builder = self.client.create_payment_order(
+            &input.collection_id,
+            &input.bank_code,
+            &input.acc_no,
+            &input.name,
+            &input.description,
+            input.total,
+        );
+        if let Some(e) = &input.email {
+            builder = builder.email(e);
+        }
+        if let Some(n) = input.recipient_notification {
+            builder = builder.recipient_notification(n);
+        }
+        if let Some(r) = &input.reference_id {
+            builder = builder.reference_id(r);
+        }
+        match builder.send().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Get the remaining amount that can be paid out through payment orders")]
+    async fn get_payment_order_limit(&self) -> String {
+        match self.client.get_payment_order_limit().await {
+            Ok(r) => serde_json::to_string_pretty(&r).unwrap_or_else(|e| e.to_string()),
             Err(e) => format!("Error: {}", e),
         }
     }
@@ -292,7 +744,7 @@ impl BillplzMcp {
 impl ServerHandler for BillplzMcp {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            instructions: Some("Billplz payment gateway MCP server. Manage collections, bills, payouts, and bank verifications.".into()),
+            instructions: Some("Billplz payment gateway MCP server. Manage collections, open collections, bills, payouts, payment orders, and bank verifications.".into()),
             ..Default::default()
         }
     }
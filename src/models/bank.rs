@@ -4,6 +4,64 @@ use serde::{Deserialize, Serialize};
 pub struct FpxBank {
     pub bank_code: String,
     pub bank_name: String,
+    /// Whether the bank is currently accepting FPX payments.
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// An FPX bank code such as `MB2U0227`, used to send payers straight to their bank.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FpxBankCode(String);
+
+impl FpxBankCode {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(code.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for FpxBankCode {
+    fn from(code: &str) -> Self {
+        Self::new(code)
+    }
+}
+
+impl From<String> for FpxBankCode {
+    fn from(code: String) -> Self {
+        Self(code)
+    }
+}
+
+impl From<&FpxBank> for FpxBankCode {
+    fn from(bank: &FpxBank) -> Self {
+        Self::new(bank.bank_code.clone())
+    }
+}
+
+impl std::fmt::Display for FpxBankCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A bank entry as returned by GET /api/v3/fpx_banks, where `name` is the bank code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpxBankStatus {
+    pub name: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FpxBankList {
+    pub banks: Vec<FpxBankStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,3 +72,25 @@ pub struct Bank {
     pub code: String,
     pub organization: bool,
 }
+
+string_enum! {
+    /// Verification status of a bank account.
+    pub enum BankVerificationStatus {
+        Pending => "pending",
+        Verified => "verified",
+        Unverified => "unverified",
+        Rejected => "rejected",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankVerificationResponse {
+    pub name: String,
+    pub id_no: String,
+    pub acc_no: String,
+    pub code: String,
+    pub organization: bool,
+    #[serde(default)]
+    pub authorization_date: Option<String>,
+    pub status: BankVerificationStatus,
+}
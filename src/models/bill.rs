@@ -1,5 +1,22 @@
 use serde::{Deserialize, Serialize};
 
+use super::bank::{FpxBank, FpxBankCode};
+use super::money::Money;
+use crate::error::BillplzError;
+
+/// `reference_1_label` Billplz looks for when preselecting the payer's bank.
+pub const BANK_CODE_LABEL: &str = "Bank Code";
+
+string_enum! {
+    /// State of a bill as reported by Billplz.
+    pub enum BillState {
+        Due => "due",
+        Paid => "paid",
+        Deleted => "deleted",
+        Overdue => "overdue",
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bill {
     pub collection_id: String,
@@ -7,7 +24,7 @@ pub struct Bill {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mobile: Option<String>,
     pub name: String,
-    pub amount: i64,
+    pub amount: Money,
     pub callback_url: String,
     pub description: String,
     pub due_at: String,
@@ -33,7 +50,7 @@ pub struct BillResponse {
     #[serde(default)]
     pub mobile: Option<String>,
     pub name: String,
-    pub amount: i64,
+    pub amount: Money,
     pub callback_url: String,
     pub description: String,
     pub due_at: String,
@@ -50,11 +67,195 @@ pub struct BillResponse {
     #[serde(default)]
     pub reference_2: Option<String>,
     pub paid: bool,
-    pub state: String,
+    pub state: BillState,
     #[serde(default)]
-    pub paid_amount: i64,
+    pub paid_amount: Money,
     #[serde(default)]
     pub url: Option<String>,
     #[serde(default)]
     pub paid_at: Option<String>,
 }
+
+impl BillResponse {
+    /// The preferred bank set with `CreateBillBuilder::preferred_bank`, if any.
+    pub fn preferred_bank(&self) -> Option<FpxBankCode> {
+        match (&self.reference_1_label, &self.reference_1) {
+            (Some(label), Some(code)) if label == BANK_CODE_LABEL => Some(code.as_str().into()),
+            _ => None,
+        }
+    }
+
+    /// Builds the URL to send the payer to. With `auto_submit`, the Billplz bill
+    /// page is skipped and the payer lands directly on their bank's login page.
+    ///
+    /// The bill's preferred bank is checked against `options.banks`, or the
+    /// built-in FPX bank list for `options.environment`, and must be active.
+    /// One of the two is required with `auto_submit`.
+    pub fn checkout_url(&self, options: &CheckoutOptions) -> Result<String, BillplzError> {
+        let url = self
+            .url
+            .as_deref()
+            .ok_or_else(|| BillplzError::InvalidPayload("bill has no url".into()))?;
+
+        let Some(auto_submit) = options.auto_submit else {
+            return Ok(parse_bill_url(url)?.into());
+        };
+
+        let code = self.preferred_bank().ok_or_else(|| {
+            BillplzError::InvalidPayload(
+                "auto_submit requires a bill created with preferred_bank".into(),
+            )
+        })?;
+        let known;
+        let banks = match (&options.banks, &options.environment) {
+            (Some(banks), _) => banks,
+            (None, Some(environment)) => {
+                known = crate::api::bank::known_banks(Some(environment));
+                &known
+            }
+            (None, None) => {
+                return Err(BillplzError::InvalidPayload(
+                    "auto_submit needs the FPX bank list: set CheckoutOptions::banks or \
+                     CheckoutOptions::environment"
+                        .into(),
+                ))
+            }
+        };
+        match banks.iter().find(|b| b.bank_code == code.as_str()) {
+            Some(bank) if bank.active => {}
+            Some(_) => {
+                return Err(BillplzError::InvalidPayload(format!(
+                    "FPX bank {} is not active",
+                    code
+                )))
+            }
+            None => {
+                return Err(BillplzError::InvalidPayload(format!(
+                    "unknown FPX bank code {}",
+                    code
+                )))
+            }
+        }
+
+        with_auto_submit(url, auto_submit)
+    }
+}
+
+fn parse_bill_url(url: &str) -> Result<reqwest::Url, BillplzError> {
+    reqwest::Url::parse(url)
+        .map_err(|e| BillplzError::InvalidPayload(format!("invalid bill url: {}", e)))
+}
+
+/// Appends `auto_submit` to a bill URL without checking the preferred bank.
+pub(crate) fn with_auto_submit(url: &str, mode: AutoSubmit) -> Result<String, BillplzError> {
+    let mut url = parse_bill_url(url)?;
+    url.query_pairs_mut()
+        .append_pair("auto_submit", mode.as_str());
+    Ok(url.into())
+}
+
+/// How the bill page hands over to the payment gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoSubmit {
+    /// Skip the bill page and submit straight to the preferred bank.
+    Enabled,
+    /// Show the bill page with the preferred bank preselected.
+    Disabled,
+}
+
+impl AutoSubmit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoSubmit::Enabled => "true",
+            AutoSubmit::Disabled => "false",
+        }
+    }
+}
+
+/// Options for `BillResponse::checkout_url`.
+#[derive(Debug, Clone, Default)]
+pub struct CheckoutOptions {
+    auto_submit: Option<AutoSubmit>,
+    banks: Option<Vec<FpxBank>>,
+    environment: Option<crate::Environment>,
+}
+
+impl CheckoutOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn auto_submit(mut self, mode: AutoSubmit) -> Self {
+        self.auto_submit = Some(mode);
+        self
+    }
+
+    /// Validate the preferred bank against this list, e.g. from `fetch_fpx_banks`,
+    /// instead of the built-in one.
+    pub fn banks(mut self, banks: Vec<FpxBank>) -> Self {
+        self.banks = Some(banks);
+        self
+    }
+
+    /// Validate the preferred bank against the built-in FPX bank list for this
+    /// environment. Staging also accepts the test banks.
+    pub fn environment(mut self, environment: crate::Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+}
+
+/// Payload POSTed by Billplz to a bill's `callback_url` once its state changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackPayload {
+    pub id: String,
+    pub collection_id: String,
+    pub paid: bool,
+    pub state: BillState,
+    pub amount: Money,
+    pub paid_amount: Money,
+    pub due_at: String,
+    pub email: String,
+    #[serde(default)]
+    pub mobile: Option<String>,
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub paid_at: Option<String>,
+    #[serde(default)]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    pub transaction_status: Option<String>,
+    pub x_signature: String,
+}
+
+/// Query parameters (`billplz[...]`) appended to a bill's `redirect_url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedirectPayload {
+    pub id: String,
+    pub paid: bool,
+    #[serde(default)]
+    pub paid_at: Option<String>,
+    #[serde(default)]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    pub transaction_status: Option<String>,
+    pub x_signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub completed_at: Option<String>,
+    #[serde(default)]
+    pub payment_channel: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillTransactions {
+    pub bill_id: String,
+    pub transactions: Vec<Transaction>,
+    pub page: u32,
+}
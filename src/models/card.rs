@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+
+/// Card details sent to POST /api/v4/cards. `Debug` masks the card number and CVV.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub name: String,
+    pub email: String,
+    pub phone: String,
+    pub card_number: String,
+    pub cvv: String,
+    /// Expiry as `MM/YY`.
+    pub expiry: String,
+}
+
+impl std::fmt::Debug for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let last4 = self
+            .card_number
+            .get(self.card_number.len().saturating_sub(4)..)
+            .unwrap_or_default();
+        f.debug_struct("Card")
+            .field("name", &self.name)
+            .field("email", &self.email)
+            .field("phone", &self.phone)
+            .field("card_number", &format_args!("****{}", last4))
+            .field("cvv", &format_args!("***"))
+            .field("expiry", &self.expiry)
+            .finish()
+    }
+}
+
+string_enum! {
+    /// Status of a tokenized card.
+    pub enum CardStatus {
+        Active => "active",
+        Deleted => "deleted",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardResponse {
+    pub id: String,
+    /// Last four digits of the card.
+    pub card_number: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Secret token required, together with `id`, to charge or delete the card.
+    pub token: String,
+    pub status: CardStatus,
+}
+
+/// Body of DELETE /api/v4/cards/{id}. The token goes in the body so it stays
+/// out of URLs, proxy logs and error messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteCardRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeRequest {
+    pub card_id: String,
+    pub token: String,
+}
+
+string_enum! {
+    /// Outcome of charging a bill with a card token.
+    pub enum ChargeStatus {
+        Success => "success",
+        Failed => "failed",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeResponse {
+    pub amount: Money,
+    pub status: ChargeStatus,
+    #[serde(default)]
+    pub reference_id: Option<String>,
+    pub bill_id: String,
+}
@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use super::money::Money;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SplitPayment {
     pub email: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub fixed_cut: Option<i64>,
+    pub fixed_cut: Option<Money>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variable_cut: Option<String>,
     pub stack_order: i32,
@@ -39,3 +41,9 @@ pub struct CollectionResponse {
     pub logo: Option<Logo>,
     pub status: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionList {
+    pub collections: Vec<CollectionResponse>,
+    pub page: u32,
+}
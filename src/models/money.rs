@@ -0,0 +1,124 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// An amount in Malaysian Ringgit, stored as sen (cents) like the Billplz API.
+///
+/// Serializes as the bare integer so existing JSON round-trips unchanged.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Self(cents)
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Option<Money> {
+        self.0.checked_mul(factor).map(Money)
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+}
+
+impl From<i64> for Money {
+    fn from(cents: i64) -> Self {
+        Self(cents)
+    }
+}
+
+impl From<i32> for Money {
+    fn from(cents: i32) -> Self {
+        Self(cents.into())
+    }
+}
+
+impl From<Money> for i64 {
+    fn from(money: Money) -> Self {
+        money.0
+    }
+}
+
+impl PartialEq<i64> for Money {
+    fn eq(&self, other: &i64) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Formats as `RM 12.34`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        write!(f, "{}RM {}.{:02}", sign, abs / 100, abs % 100)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid ringgit amount: {0:?}")]
+pub struct ParseMoneyError(String);
+
+/// Parses `RM 12.34`, `RM12.34`, `12.34`, `1,234.5` or `12` (ringgit, not sen).
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseMoneyError(s.to_string());
+
+        let trimmed = s.trim();
+        let (negative, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, trimmed),
+        };
+        let rest = rest
+            .strip_prefix("RM")
+            .or_else(|| rest.strip_prefix("rm"))
+            .unwrap_or(rest)
+            .trim_start()
+            .replace(',', "");
+
+        let (whole, frac) = match rest.split_once('.') {
+            Some((w, f)) => (w, f),
+            None => (rest.as_str(), ""),
+        };
+        if whole.is_empty()
+            || frac.len() > 2
+            || !whole.bytes().all(|b| b.is_ascii_digit())
+            || !frac.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(err());
+        }
+
+        let whole: i64 = whole.parse().map_err(|_| err())?;
+        let frac: i64 = match frac.len() {
+            0 => 0,
+            1 => frac.parse::<i64>().map_err(|_| err())? * 10,
+            _ => frac.parse().map_err(|_| err())?,
+        };
+        let cents = whole
+            .checked_mul(100)
+            .and_then(|c| c.checked_add(frac))
+            .ok_or_else(err)?;
+
+        Ok(Money(if negative { -cents } else { cents }))
+    }
+}
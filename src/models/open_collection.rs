@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use super::collection::SplitPayment;
+use super::money::Money;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenCollection {
+    pub title: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_amount: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_quantity: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_button: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_1_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_2_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_header: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_payments: Option<Vec<SplitPayment>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Photo {
+    #[serde(default)]
+    pub retina_url: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenCollectionResponse {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub reference_1_label: Option<String>,
+    #[serde(default)]
+    pub reference_2_label: Option<String>,
+    #[serde(default)]
+    pub email_link: Option<String>,
+    #[serde(default)]
+    pub amount: Option<Money>,
+    #[serde(default)]
+    pub fixed_amount: Option<bool>,
+    #[serde(default)]
+    pub tax: Option<i32>,
+    #[serde(default)]
+    pub fixed_quantity: Option<bool>,
+    #[serde(default)]
+    pub payment_button: Option<String>,
+    #[serde(default)]
+    pub photo: Option<Photo>,
+    #[serde(default)]
+    pub split_header: Option<bool>,
+    #[serde(default)]
+    pub split_payments: Option<Vec<SplitPayment>>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub redirect_uri: Option<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenCollectionList {
+    pub open_collections: Vec<OpenCollectionResponse>,
+    pub page: u32,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// A payment channel (FPX, card, e-wallet, BNPL, ...) that can be enabled on a collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethod {
+    pub code: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodList {
+    pub payment_methods: Vec<PaymentMethod>,
+}
+
+/// Request body for PUT /api/v3/collections/{id}/payment_methods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodCodes {
+    pub payment_methods: Vec<PaymentMethodCode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodCode {
+    pub code: String,
+}
+
+/// A bank or channel that can be preselected on a bill, as returned by
+/// GET /api/v4/payment_gateways.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentGateway {
+    pub code: String,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub extras: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentGatewayList {
+    pub payment_gateways: Vec<PaymentGateway>,
+}
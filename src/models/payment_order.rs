@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrderCollection {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+    pub epoch: i64,
+    pub checksum: String,
+}
+
+string_enum! {
+    /// Status of a payment order collection.
+    pub enum PaymentOrderCollectionStatus {
+        Active => "active",
+        Processing => "processing",
+        Completed => "completed",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrderCollectionResponse {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub payment_orders_count: i64,
+    #[serde(default)]
+    pub paid_amount: Money,
+    pub status: PaymentOrderCollectionStatus,
+    #[serde(default)]
+    pub callback_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrder {
+    pub payment_order_collection_id: String,
+    pub bank_code: String,
+    pub bank_account_number: String,
+    pub name: String,
+    pub description: String,
+    pub total: Money,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_notification: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<String>,
+    pub epoch: i64,
+    pub checksum: String,
+}
+
+string_enum! {
+    /// Status of a payment order.
+    pub enum PaymentOrderStatus {
+        Enqueued => "enqueued",
+        Processing => "processing",
+        Completed => "completed",
+        Refunded => "refunded",
+        Cancelled => "cancelled",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrderResponse {
+    pub id: String,
+    pub payment_order_collection_id: String,
+    pub bank_code: String,
+    pub bank_account_number: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    pub status: PaymentOrderStatus,
+    #[serde(default)]
+    pub notification: Option<bool>,
+    #[serde(default)]
+    pub recipient_notification: Option<bool>,
+    #[serde(default)]
+    pub reference_id: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub total: Money,
+}
+
+/// Remaining amount that can be paid out through payment orders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrderLimit {
+    pub total: Money,
+}
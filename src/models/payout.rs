@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::money::Money;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payout {
     pub mass_payment_instruction_collection_id: String,
@@ -8,5 +10,37 @@ pub struct Payout {
     pub identity_number: String,
     pub name: String,
     pub description: String,
-    pub total: i64,
+    pub total: Money,
+}
+
+string_enum! {
+    /// Status of a mass payment instruction.
+    pub enum PayoutStatus {
+        Enqueued => "enqueued",
+        Processing => "processing",
+        Completed => "completed",
+        Refunded => "refunded",
+        Cancelled => "cancelled",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutResponse {
+    pub id: String,
+    pub mass_payment_instruction_collection_id: String,
+    pub bank_code: String,
+    pub bank_account_number: String,
+    pub identity_number: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub notification: Option<bool>,
+    #[serde(default)]
+    pub recipient_notification: Option<bool>,
+    pub total: Money,
+    #[serde(default)]
+    pub reference_id: Option<String>,
+    pub status: PayoutStatus,
 }
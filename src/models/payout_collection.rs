@@ -1,6 +1,34 @@
 use serde::{Deserialize, Serialize};
 
+use super::money::Money;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PayoutCollection {
     pub title: String,
 }
+
+string_enum! {
+    /// Status of a mass payment instruction collection.
+    pub enum PayoutCollectionStatus {
+        Active => "active",
+        Processing => "processing",
+        Completed => "completed",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutCollectionResponse {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub mass_payment_instructions_count: i64,
+    #[serde(default)]
+    pub paid_amount: Money,
+    pub status: PayoutCollectionStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutCollectionList {
+    pub mass_payment_instruction_collections: Vec<PayoutCollectionResponse>,
+    pub page: u32,
+}
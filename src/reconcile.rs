@@ -0,0 +1,230 @@
+//! Compares a local ledger of bills against what Billplz reports.
+//!
+//! Each [`LedgerEntry`] names a bill and what the ledger believes about it.
+//! [`BillplzClient::reconcile`] fetches every bill and classifies the entry,
+//! so month-end checks only need to look at the rows that are not `matched`.
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::models::bill::{BillResponse, BillState};
+use crate::models::money::Money;
+use crate::{BillplzClient, BillplzError};
+
+/// Format Billplz uses for `paid_at`, e.g. `2015-03-09 16:23:59 +0800`.
+const PAID_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+/// What the local ledger expects for one bill. Only `bill_id` and `paid` are
+/// required; the other fields are compared when present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub bill_id: String,
+    pub paid: bool,
+    /// Expected `paid_amount` for paid bills, or the bill `amount` otherwise.
+    #[serde(default)]
+    pub amount: Option<Money>,
+    #[serde(default)]
+    pub state: Option<BillState>,
+    /// A full Billplz timestamp, or just the date (`YYYY-MM-DD`).
+    #[serde(default)]
+    pub paid_at: Option<String>,
+}
+
+/// How a ledger entry compares to the bill on Billplz, most serious first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileStatus {
+    /// Billplz has no bill with this ID.
+    Missing,
+    /// The bill could not be fetched; see `details`.
+    Failed,
+    /// Paid on Billplz but not in the ledger.
+    PaidRemotelyPendingLocally,
+    /// Paid in the ledger but not on Billplz.
+    PaidLocallyPendingRemotely,
+    AmountMismatch,
+    /// `paid` and the amount agree, but `state` or `paid_at` do not.
+    Mismatch,
+    Matched,
+}
+
+/// One line of the report. `row` is the 1-based position in the ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileRow {
+    pub row: usize,
+    pub bill_id: String,
+    pub status: ReconcileStatus,
+    pub local_paid: bool,
+    pub remote_paid: Option<bool>,
+    pub local_amount: Option<Money>,
+    pub remote_amount: Option<Money>,
+    pub local_state: Option<BillState>,
+    pub remote_state: Option<BillState>,
+    pub local_paid_at: Option<String>,
+    pub remote_paid_at: Option<String>,
+    /// Every difference found, or the fetch error, separated by `; `.
+    pub details: Option<String>,
+}
+
+/// Row counts per status.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconcileSummary {
+    pub matched: usize,
+    pub amount_mismatch: usize,
+    pub paid_remotely_pending_locally: usize,
+    pub paid_locally_pending_remotely: usize,
+    pub mismatch: usize,
+    pub missing: usize,
+    pub failed: usize,
+}
+
+impl ReconcileSummary {
+    fn count(&mut self, status: ReconcileStatus) {
+        let counter = match status {
+            ReconcileStatus::Matched => &mut self.matched,
+            ReconcileStatus::AmountMismatch => &mut self.amount_mismatch,
+            ReconcileStatus::PaidRemotelyPendingLocally => &mut self.paid_remotely_pending_locally,
+            ReconcileStatus::PaidLocallyPendingRemotely => &mut self.paid_locally_pending_remotely,
+            ReconcileStatus::Mismatch => &mut self.mismatch,
+            ReconcileStatus::Missing => &mut self.missing,
+            ReconcileStatus::Failed => &mut self.failed,
+        };
+        *counter += 1;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    pub summary: ReconcileSummary,
+    /// One row per ledger entry, in ledger order.
+    pub rows: Vec<ReconcileRow>,
+}
+
+impl ReconcileReport {
+    /// Rows whose status is anything but `matched`.
+    pub fn discrepancies(&self) -> impl Iterator<Item = &ReconcileRow> {
+        self.rows
+            .iter()
+            .filter(|r| r.status != ReconcileStatus::Matched)
+    }
+}
+
+impl LedgerEntry {
+    fn compare(self, row: usize, fetched: Result<BillResponse, BillplzError>) -> ReconcileRow {
+        let mut result = ReconcileRow {
+            row,
+            status: ReconcileStatus::Matched,
+            local_paid: self.paid,
+            remote_paid: None,
+            local_amount: self.amount,
+            remote_amount: None,
+            local_state: self.state,
+            remote_state: None,
+            local_paid_at: self.paid_at,
+            remote_paid_at: None,
+            details: None,
+            bill_id: self.bill_id,
+        };
+        let bill = match fetched {
+            Ok(bill) => bill,
+            Err(e) => {
+                result.status = if e.is_not_found() {
+                    ReconcileStatus::Missing
+                } else {
+                    ReconcileStatus::Failed
+                };
+                result.details = Some(e.to_string());
+                return result;
+            }
+        };
+
+        let remote_amount = if bill.paid {
+            bill.paid_amount
+        } else {
+            bill.amount
+        };
+        result.remote_paid = Some(bill.paid);
+        result.remote_amount = Some(remote_amount);
+        result.remote_state = Some(bill.state.clone());
+        result.remote_paid_at = bill.paid_at.clone();
+
+        let mut differences = Vec::new();
+        let mut statuses = Vec::new();
+        if result.local_paid != bill.paid {
+            differences.push(format!(
+                "paid: local {}, remote {}",
+                result.local_paid, bill.paid
+            ));
+            statuses.push(if bill.paid {
+                ReconcileStatus::PaidRemotelyPendingLocally
+            } else {
+                ReconcileStatus::PaidLocallyPendingRemotely
+            });
+        }
+        if let Some(local) = result.local_amount.filter(|a| *a != remote_amount) {
+            let field = if bill.paid { "paid_amount" } else { "amount" };
+            differences.push(format!(
+                "{}: local {}, remote {}",
+                field, local, remote_amount
+            ));
+            statuses.push(ReconcileStatus::AmountMismatch);
+        }
+        if let Some(local) = result.local_state.as_ref().filter(|s| **s != bill.state) {
+            differences.push(format!("state: local {}, remote {}", local, bill.state));
+            statuses.push(ReconcileStatus::Mismatch);
+        }
+        if let Some(local) = &result.local_paid_at {
+            if !same_paid_at(local, bill.paid_at.as_deref()) {
+                differences.push(format!(
+                    "paid_at: local {}, remote {}",
+                    local,
+                    bill.paid_at.as_deref().unwrap_or("none")
+                ));
+                statuses.push(ReconcileStatus::Mismatch);
+            }
+        }
+
+        if let Some(status) = statuses.into_iter().min() {
+            result.status = status;
+            result.details = Some(differences.join("; "));
+        }
+        result
+    }
+}
+
+/// Compares timestamps as instants, or only the date when the ledger has no time.
+fn same_paid_at(local: &str, remote: Option<&str>) -> bool {
+    let Some(remote) = remote else {
+        return false;
+    };
+    let parse = |s: &str| chrono::DateTime::parse_from_str(s.trim(), PAID_AT_FORMAT);
+    if let (Ok(local), Ok(remote)) = (parse(local), parse(remote)) {
+        return local == remote;
+    }
+    let local = local.trim();
+    if chrono::NaiveDate::parse_from_str(local, "%Y-%m-%d").is_ok() {
+        return remote.trim().starts_with(local);
+    }
+    local == remote.trim()
+}
+
+impl BillplzClient {
+    /// Fetches the bill for every ledger entry, up to `concurrency` at a time,
+    /// and reports how each compares. Fetch errors are recorded per row.
+    pub async fn reconcile(&self, ledger: Vec<LedgerEntry>, concurrency: usize) -> ReconcileReport {
+        let rows: Vec<ReconcileRow> = futures::stream::iter(ledger.into_iter().enumerate())
+            .map(|(i, entry)| async move {
+                let fetched = self.get_bill(&entry.bill_id).await;
+                entry.compare(i + 1, fetched)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut summary = ReconcileSummary::default();
+        for row in &rows {
+            summary.count(row.status);
+        }
+        ReconcileReport { summary, rows }
+    }
+}
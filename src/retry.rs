@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+
+use crate::error::BillplzError;
+use crate::transport::{HttpRequest, HttpResponse};
+
+/// Controls how `BillplzClient` retries transient failures (429, 5xx, timeouts).
+///
+/// Idempotent requests (GET, HEAD, PUT, DELETE) are retried. A POST is only
+/// retried when its idempotency key has been claimed in the client's
+/// `IdempotencyStore`, and never after a timeout: Billplz does not deduplicate
+/// POSTs, so a request that may have arrived is not sent again.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on every subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including `Retry-After`.
+    pub max_delay: Duration,
+    /// Randomize each delay between half and the full backoff.
+    pub jitter: bool,
+    /// Wait for the server's `Retry-After` header when present.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// `claimed` marks a POST whose idempotency key is held as pending.
+    pub(crate) fn allows(&self, request: &HttpRequest, claimed: bool) -> bool {
+        match request.method {
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE => true,
+            Method::POST => claimed,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn should_retry_status(&self, status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// A timed-out POST may have reached Billplz, so only a failed connection
+    /// lets it be sent again.
+    pub(crate) fn should_retry_error(&self, request: &HttpRequest, err: &BillplzError) -> bool {
+        matches!(err, BillplzError::Http(e)
+            if e.is_connect() || (e.is_timeout() && request.method != Method::POST))
+    }
+
+    /// Delay before retry number `retry` (1-based), honouring `Retry-After` if given.
+    pub(crate) fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after.min(self.max_delay);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_delay);
+        if self.jitter {
+            let half = backoff / 2;
+            half + backoff.mul_f64(fastrand::f64() * 0.5)
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds.
+pub(crate) fn retry_after(resp: &HttpResponse) -> Option<Duration> {
+    resp.headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+
+use crate::error::BillplzError;
+use crate::models::bill::{CallbackPayload, RedirectPayload};
+
+type HmacSha256 = Hmac<Sha256>;
+type HmacSha512 = Hmac<Sha512>;
+
+/// Verifies X-Signature on Billplz callbacks and redirects.
+///
+/// The key is the X-Signature key shown in the Billplz account settings.
+#[derive(Debug, Clone)]
+pub struct XSignature {
+    key: String,
+}
+
+impl XSignature {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// Builds the source string Billplz signs: every `key+value` pair except
+    /// `x_signature`, sorted case-insensitively and joined with `|`.
+    pub fn source_string<K, V>(params: impl IntoIterator<Item = (K, V)>) -> String
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut parts: Vec<String> = params
+            .into_iter()
+            .filter(|(k, _)| k.as_ref() != "x_signature")
+            .map(|(k, v)| format!("{}{}", k.as_ref(), v.as_ref()))
+            .collect();
+        parts.sort_by_key(|p| p.to_lowercase());
+        parts.join("|")
+    }
+
+    /// Computes the hex-encoded HMAC-SHA256 signature for the given parameters.
+    pub fn sign<K, V>(&self, params: impl IntoIterator<Item = (K, V)>) -> String
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut mac = self.mac();
+        mac.update(Self::source_string(params).as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Checks `signature` against the parameters using a constant-time comparison.
+    pub fn verify<K, V>(&self, params: impl IntoIterator<Item = (K, V)>, signature: &str) -> bool
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let Ok(expected) = hex::decode(signature) else {
+            return false;
+        };
+        let mut mac = self.mac();
+        mac.update(Self::source_string(params).as_bytes());
+        mac.verify_slice(&expected).is_ok()
+    }
+
+    /// Computes the hex-encoded HMAC-SHA512 `checksum` used by the v5 Payment Order
+    /// API: the given values concatenated in order, without separators.
+    pub fn checksum<S: AsRef<str>>(&self, parts: impl IntoIterator<Item = S>) -> String {
+        let mut mac = HmacSha512::new_from_slice(self.key.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        for part in parts {
+            mac.update(part.as_ref().as_bytes());
+        }
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Verifies callback parameters (the decoded POST form) and returns the typed payload.
+    pub fn verify_callback<K, V>(
+        &self,
+        params: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<CallbackPayload, BillplzError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let params: HashMap<String, String> = params
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self.check(&params, &params)?;
+
+        Ok(CallbackPayload {
+            id: required(&params, "id")?,
+            collection_id: required(&params, "collection_id")?,
+            paid: parse_bool(&params, "paid")?,
+            state: required(&params, "state")?.into(),
+            amount: parse_i64(&params, "amount")?.into(),
+            paid_amount: parse_i64(&params, "paid_amount")?.into(),
+            due_at: required(&params, "due_at")?,
+            email: required(&params, "email")?,
+            mobile: optional(&params, "mobile"),
+            name: required(&params, "name")?,
+            url: required(&params, "url")?,
+            paid_at: optional(&params, "paid_at"),
+            transaction_id: optional(&params, "transaction_id"),
+            transaction_status: optional(&params, "transaction_status"),
+            x_signature: required(&params, "x_signature")?,
+        })
+    }
+
+    /// Verifies an `application/x-www-form-urlencoded` callback body.
+    pub fn verify_callback_form(&self, body: &str) -> Result<CallbackPayload, BillplzError> {
+        self.verify_callback(form_urlencoded::parse(body.as_bytes()).into_owned())
+    }
+
+    /// Verifies redirect parameters. Keys may be given as `billplz[id]` or `id`.
+    pub fn verify_redirect<K, V>(
+        &self,
+        params: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<RedirectPayload, BillplzError>
+    where
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let params: HashMap<String, String> = params
+            .into_iter()
+            .map(|(k, v)| {
+                let k = k.as_ref();
+                let k = k
+                    .strip_prefix("billplz[")
+                    .and_then(|s| s.strip_suffix(']'))
+                    .unwrap_or(k);
+                (k.to_string(), v.into())
+            })
+            .collect();
+        // Redirect keys are signed as `billplz` followed by the inner key.
+        let signed: HashMap<String, String> = params
+            .iter()
+            .filter(|(k, _)| k.as_str() != "x_signature")
+            .map(|(k, v)| (format!("billplz{}", k), v.clone()))
+            .collect();
+        self.check(&signed, &params)?;
+
+        Ok(RedirectPayload {
+            id: required(&params, "id")?,
+            paid: parse_bool(&params, "paid")?,
+            paid_at: optional(&params, "paid_at"),
+            transaction_id: optional(&params, "transaction_id"),
+            transaction_status: optional(&params, "transaction_status"),
+            x_signature: required(&params, "x_signature")?,
+        })
+    }
+
+    /// Verifies a raw redirect query string, e.g. `billplz%5Bid%5D=...&billplz%5Bpaid%5D=true`.
+    pub fn verify_redirect_query(&self, query: &str) -> Result<RedirectPayload, BillplzError> {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        self.verify_redirect(form_urlencoded::parse(query.as_bytes()).into_owned())
+    }
+
+    fn check(
+        &self,
+        signed: &HashMap<String, String>,
+        params: &HashMap<String, String>,
+    ) -> Result<(), BillplzError> {
+        // An unsigned request is as untrusted as a badly signed one.
+        let Some(signature) = params.get("x_signature").filter(|s| !s.is_empty()) else {
+            return Err(BillplzError::InvalidSignature);
+        };
+        if self.verify(signed, signature) {
+            Ok(())
+        } else {
+            Err(BillplzError::InvalidSignature)
+        }
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(self.key.as_bytes()).expect("HMAC accepts keys of any length")
+    }
+}
+
+/// HTTP status for refusing a callback or redirect: `401` for a missing or bad
+/// signature, `500` for a missing key and `400` for anything undecodable.
+/// Shared by the framework extractors and `billplz webhook listen`.
+pub(crate) fn rejection_status(err: &BillplzError) -> u16 {
+    match err {
+        BillplzError::InvalidSignature => 401,
+        BillplzError::Config(_) => 500,
+        _ => 400,
+    }
+}
+
+fn required(params: &HashMap<String, String>, key: &str) -> Result<String, BillplzError> {
+    params
+        .get(key)
+        .cloned()
+        .ok_or_else(|| BillplzError::InvalidPayload(format!("missing {}", key)))
+}
+
+fn optional(params: &HashMap<String, String>, key: &str) -> Option<String> {
+    params.get(key).filter(|v| !v.is_empty()).cloned()
+}
+
+fn parse_bool(params: &HashMap<String, String>, key: &str) -> Result<bool, BillplzError> {
+    match required(params, key)?.as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(BillplzError::InvalidPayload(format!("{} is not a boolean: {}", key, other))),
+    }
+}
+
+fn parse_i64(params: &HashMap<String, String>, key: &str) -> Result<i64, BillplzError> {
+    let value = required(params, key)?;
+    value
+        .parse()
+        .map_err(|_| BillplzError::InvalidPayload(format!("{} is not an integer: {}", key, value)))
+}
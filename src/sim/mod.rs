@@ -0,0 +1,283 @@
+//! An in-memory Billplz API for tests and local development.
+//!
+//! The simulator serves the collection, bill, bank verification, payout and
+//! payout collection endpoints with Basic auth, and adds a few `/sim/...`
+//! admin endpoints (no auth) for driving state the real API changes on its own:
+//!
+//! - `POST /sim/bills/{id}/pay` marks a bill paid and sends a signed callback
+//!   to its `callback_url`.
+//! - `POST /sim/bank_verifications/{acc_no}` with `{"status": "verified"}`.
+//! - `POST /sim/payouts/{id}` with `{"status": "completed"}`.
+
+mod state;
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{header, Method, Request, Response};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::net::TcpListener;
+
+use crate::XSignature;
+use state::{Reply, State};
+
+/// A stateful fake of the Billplz API.
+pub struct Simulator {
+    api_key: String,
+    signer: Option<XSignature>,
+    state: Mutex<State>,
+    http: reqwest::Client,
+}
+
+impl Simulator {
+    /// Accepts requests authenticated with `api_key` as the Basic auth username.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            signer: None,
+            state: Mutex::new(State::default()),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Signs callbacks with this key. Without one, callbacks are sent unsigned.
+    pub fn x_signature_key(mut self, key: impl Into<String>) -> Self {
+        self.signer = Some(XSignature::new(key));
+        self
+    }
+
+    /// Binds `127.0.0.1:port` and serves until the process is stopped.
+    pub async fn listen(self, port: u16) -> Result<(), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).await?;
+        eprintln!(
+            "Billplz simulator listening on http://{}",
+            listener.local_addr()?
+        );
+        self.serve(listener).await
+    }
+
+    /// Serves on a free local port in the background and returns its base URL,
+    /// ready for `BillplzClient::builder().base_url(..)`.
+    pub async fn spawn(self) -> std::io::Result<String> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move {
+            if let Err(e) = self.serve(listener).await {
+                eprintln!("simulator stopped: {}", e);
+            }
+        });
+        Ok(base_url)
+    }
+
+    /// Serves the API on an already bound listener.
+    pub async fn serve(self, listener: TcpListener) -> Result<(), Box<dyn std::error::Error>> {
+        let simulator = Arc::new(self);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let state = simulator.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req| {
+                    let state = state.clone();
+                    async move { Ok::<_, hyper::Error>(state.handle(req).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    eprintln!("connection error: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let query: Vec<(String, String)> = req
+            .uri()
+            .query()
+            .map(|q| form_urlencoded::parse(q.as_bytes()).into_owned().collect())
+            .unwrap_or_default();
+        let host = req
+            .headers()
+            .get(header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("localhost")
+            .to_string();
+        let authorized = self.authorized(&req);
+        let body = match req.into_body().collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => return respond(Reply::error(400, "BadRequest", e.to_string())),
+        };
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        if segments.first() == Some(&"sim") {
+            return self.handle_admin(&method, &segments[1..], &body).await;
+        }
+        if !authorized {
+            return respond(Reply::error(401, "Unauthorized", "Invalid access token"));
+        }
+
+        let page = query
+            .iter()
+            .find(|(k, _)| k == "page")
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(1);
+        let status = query
+            .iter()
+            .find(|(k, _)| k == "status")
+            .map(|(_, v)| v.as_str());
+        let base_url = format!("http://{}", host);
+
+        let mut state = self.lock();
+        let reply = match (&method, segments.as_slice()) {
+            (&Method::POST, ["api", "v4", "collections"]) => {
+                parse(&body).map(|b| state.create_collection(b))
+            }
+            (&Method::GET, ["api", "v4", "collections"]) => {
+                Ok(state.list_collections(page, status))
+            }
+            (&Method::GET, ["api", "v4", "collections", id]) => Ok(state.get_collection(id)),
+            (&Method::POST, ["api", "v3", "collections", id, "activate"]) => {
+                Ok(state.set_collection_status(id, true))
+            }
+            (&Method::POST, ["api", "v3", "collections", id, "deactivate"]) => {
+                Ok(state.set_collection_status(id, false))
+            }
+            (&Method::POST, ["api", "v3", "bills"]) => {
+                parse(&body).map(|b| state.create_bill(b, &base_url))
+            }
+            (&Method::GET, ["api", "v3", "bills", id]) => Ok(state.get_bill(id)),
+            (&Method::DELETE, ["api", "v3", "bills", id]) => Ok(state.delete_bill(id)),
+            (&Method::GET, ["api", "v3", "bills", id, "transactions"]) => {
+                Ok(state.bill_transactions(id, page, status))
+            }
+            (&Method::GET, ["api", "v3", "fpx_banks"]) => Ok(state.fpx_banks()),
+            (&Method::POST, ["api", "v3", "bank_verification_services"]) => {
+                parse(&body).map(|b| state.create_bank_verification(b))
+            }
+            (&Method::GET, ["api", "v3", "bank_verification_services", acc_no]) => {
+                Ok(state.get_bank_verification(acc_no))
+            }
+            (&Method::POST, ["api", "v4", "mass_payment_instruction_collections"]) => {
+                parse(&body).map(|b| state.create_payout_collection(b))
+            }
+            (&Method::GET, ["api", "v4", "mass_payment_instruction_collections"]) => {
+                Ok(state.list_payout_collections(page))
+            }
+            (&Method::GET, ["api", "v4", "mass_payment_instruction_collections", id]) => {
+                Ok(state.get_payout_collection(id))
+            }
+            (&Method::POST, ["api", "v4", "mass_payment_instructions"]) => {
+                parse(&body).map(|b| state.create_payout(b))
+            }
+            (&Method::GET, ["api", "v4", "mass_payment_instructions", id]) => {
+                Ok(state.get_payout(id))
+            }
+            _ => Ok(route_not_found(&method, &path)),
+        };
+        respond(reply.unwrap_or_else(|e| e))
+    }
+
+    async fn handle_admin(
+        &self,
+        method: &Method,
+        segments: &[&str],
+        body: &Bytes,
+    ) -> Response<Full<Bytes>> {
+        if method != Method::POST {
+            return respond(Reply::error(405, "MethodNotAllowed", "expected a POST"));
+        }
+        let reply = match segments {
+            ["bills", id, "pay"] => return self.pay_bill(id).await,
+            ["bank_verifications", acc_no] => {
+                parse(body).map(|update| self.lock().set_bank_verification_status(acc_no, update))
+            }
+            ["payouts", id] => parse(body).map(|update| self.lock().set_payout_status(id, update)),
+            _ => Ok(Reply::not_found("Simulator endpoint")),
+        };
+        respond(reply.unwrap_or_else(|e| e))
+    }
+
+    /// Pays the bill, then delivers its callback outside the state lock.
+    async fn pay_bill(&self, id: &str) -> Response<Full<Bytes>> {
+        let paid = self.lock().pay_bill(id);
+        let (bill, mut callback) = match paid {
+            Ok(paid) => paid,
+            Err(reply) => return respond(reply),
+        };
+        if let Some(signer) = &self.signer {
+            let signature = signer.sign(callback.params.iter().map(|(k, v)| (k, v)));
+            callback.params.push(("x_signature".into(), signature));
+        }
+
+        let form = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&callback.params)
+            .finish();
+        let delivered = self
+            .http
+            .post(&callback.url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(form)
+            .send()
+            .await;
+        let callback_result = match delivered {
+            Ok(resp) => json!({ "url": callback.url, "status": resp.status().as_u16() }),
+            Err(e) => json!({ "url": callback.url, "error": e.to_string() }),
+        };
+        respond(Reply::ok(
+            json!({ "bill": bill, "callback": callback_result }),
+        ))
+    }
+
+    fn authorized<B>(&self, req: &Request<B>) -> bool {
+        let Some(credentials) = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Basic "))
+        else {
+            return false;
+        };
+        let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(credentials.trim())
+        else {
+            return false;
+        };
+        let decoded = String::from_utf8_lossy(&decoded);
+        let username = decoded.split(':').next().unwrap_or_default();
+        username == self.api_key
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("simulator state poisoned")
+    }
+}
+
+fn parse<T: DeserializeOwned>(body: &Bytes) -> Result<T, Reply> {
+    serde_json::from_slice(body)
+        .map_err(|e| Reply::error(422, "RecordInvalid", vec![e.to_string()]))
+}
+
+fn route_not_found(method: &Method, path: &str) -> Reply {
+    Reply::error(404, "NotFound", format!("No route for {} {}", method, path))
+}
+
+fn respond(reply: Reply) -> Response<Full<Bytes>> {
+    let mut resp = Response::new(Full::new(Bytes::from(reply.body.to_string())));
+    *resp.status_mut() = hyper::StatusCode::from_u16(reply.status).unwrap_or(hyper::StatusCode::OK);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    resp
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api::bank::{is_payout_bank_code, known_banks};
+use crate::client::Environment;
+use crate::models::bank::{Bank, BankVerificationResponse, BankVerificationStatus};
+use crate::models::bill::{Bill, BillResponse, BillState, Transaction};
+use crate::models::collection::{Collection, CollectionResponse};
+use crate::models::money::Money;
+use crate::models::payout::{Payout, PayoutResponse, PayoutStatus};
+use crate::models::payout_collection::{
+    PayoutCollection, PayoutCollectionResponse, PayoutCollectionStatus,
+};
+
+/// Items per page on list endpoints, as on Billplz.
+const PAGE_SIZE: usize = 15;
+
+/// A JSON reply from the simulator.
+pub(crate) struct Reply {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Reply {
+    pub fn ok(body: impl serde::Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+        }
+    }
+
+    pub fn error(status: u16, error_type: &str, message: impl Into<Value>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "type": error_type, "message": message.into() } }),
+        }
+    }
+
+    pub fn not_found(what: &str) -> Self {
+        Self::error(404, "RecordNotFound", format!("{} not found", what))
+    }
+
+    pub fn invalid(messages: &[&str]) -> Self {
+        Self::error(422, "RecordInvalid", messages.to_vec())
+    }
+}
+
+/// A signed callback to deliver after a bill is paid.
+pub(crate) struct Callback {
+    pub url: String,
+    pub params: Vec<(String, String)>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct StatusUpdate {
+    pub status: String,
+}
+
+/// Everything the simulator knows, kept in insertion order.
+#[derive(Default)]
+pub(crate) struct State {
+    collections: Vec<CollectionResponse>,
+    bills: Vec<BillResponse>,
+    transactions: HashMap<String, Vec<Transaction>>,
+    verifications: Vec<BankVerificationResponse>,
+    payout_collections: Vec<PayoutCollectionResponse>,
+    payouts: Vec<PayoutResponse>,
+}
+
+fn new_id() -> String {
+    std::iter::repeat_with(fastrand::alphanumeric)
+        .take(8)
+        .collect()
+}
+
+/// Current time in Malaysia, formatted like Billplz timestamps.
+fn now() -> String {
+    let myt = chrono::FixedOffset::east_opt(8 * 3600).expect("valid offset");
+    chrono::Utc::now()
+        .with_timezone(&myt)
+        .format("%Y-%m-%d %H:%M:%S %z")
+        .to_string()
+}
+
+fn page<T: Clone>(items: impl Iterator<Item = T>, page: u32) -> Vec<T> {
+    let skip = (page.max(1) as usize - 1) * PAGE_SIZE;
+    items.skip(skip).take(PAGE_SIZE).collect()
+}
+
+impl State {
+    pub fn create_collection(&mut self, body: Collection) -> Reply {
+        if body.title.trim().is_empty() {
+            return Reply::invalid(&["Title can't be blank"]);
+        }
+        let collection = CollectionResponse {
+            id: new_id(),
+            title: body.title,
+            split_header: body.split_header,
+            split_payments: body.split_payments,
+            logo: None,
+            status: "active".into(),
+        };
+        self.collections.push(collection.clone());
+        Reply::ok(collection)
+    }
+
+    pub fn get_collection(&self, id: &str) -> Reply {
+        match self.collections.iter().find(|c| c.id == id) {
+            Some(c) => Reply::ok(c),
+            None => Reply::not_found("Collection"),
+        }
+    }
+
+    pub fn list_collections(&self, page_no: u32, status: Option<&str>) -> Reply {
+        let matching = self
+            .collections
+            .iter()
+            .filter(|c| status.is_none_or(|s| c.status == s))
+            .cloned();
+        Reply::ok(json!({ "collections": page(matching, page_no), "page": page_no }))
+    }
+
+    pub fn set_collection_status(&mut self, id: &str, active: bool) -> Reply {
+        match self.collections.iter_mut().find(|c| c.id == id) {
+            Some(c) => {
+                c.status = if active { "active" } else { "inactive" }.into();
+                Reply::ok(json!({}))
+            }
+            None => Reply::not_found("Collection"),
+        }
+    }
+
+    pub fn create_bill(&mut self, body: Bill, base_url: &str) -> Reply {
+        match self.collections.iter().find(|c| c.id == body.collection_id) {
+            None => return Reply::not_found("Collection"),
+            Some(c) if c.status != "active" => return Reply::invalid(&["Collection is inactive"]),
+            Some(_) => {}
+        }
+        let mut errors = Vec::new();
+        if !body.amount.is_positive() {
+            errors.push("Amount must be greater than 0");
+        }
+        if !body.email.contains('@') && body.mobile.is_none() {
+            errors.push("Email is invalid");
+        }
+        if body.name.trim().is_empty() {
+            errors.push("Name can't be blank");
+        }
+        if !errors.is_empty() {
+            return Reply::invalid(&errors);
+        }
+
+        let id = new_id();
+        let bill = BillResponse {
+            url: Some(format!("{}/bills/{}", base_url, id)),
+            id,
+            collection_id: body.collection_id,
+            email: body.email,
+            mobile: body.mobile,
+            name: body.name,
+            amount: body.amount,
+            callback_url: body.callback_url,
+            description: body.description,
+            due_at: body.due_at,
+            redirect_url: body.redirect_url,
+            deliver: body.deliver,
+            reference_1_label: body.reference_1_label,
+            reference_1: body.reference_1,
+            reference_2_label: body.reference_2_label,
+            reference_2: body.reference_2,
+            paid: false,
+            state: BillState::Due,
+            paid_amount: Money::ZERO,
+            paid_at: None,
+        };
+        self.bills.push(bill.clone());
+        Reply::ok(bill)
+    }
+
+    pub fn get_bill(&self, id: &str) -> Reply {
+        match self.bills.iter().find(|b| b.id == id) {
+            Some(b) => Reply::ok(b),
+            None => Reply::not_found("Bill"),
+        }
+    }
+
+    pub fn delete_bill(&mut self, id: &str) -> Reply {
+        match self.bills.iter_mut().find(|b| b.id == id) {
+            None => Reply::not_found("Bill"),
+            Some(b) if b.state != BillState::Due => {
+                Reply::invalid(&["Only due bills can be deleted"])
+            }
+            Some(b) => {
+                b.state = BillState::Deleted;
+                Reply::ok(json!({}))
+            }
+        }
+    }
+
+    pub fn bill_transactions(&self, id: &str, page_no: u32, status: Option<&str>) -> Reply {
+        if !self.bills.iter().any(|b| b.id == id) {
+            return Reply::not_found("Bill");
+        }
+        let matching = self
+            .transactions
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter(|t| status.is_none_or(|s| t.status == s))
+            .cloned();
+        Reply::ok(json!({
+            "bill_id": id,
+            "transactions": page(matching, page_no),
+            "page": page_no
+        }))
+    }
+
+    /// Marks a due bill as paid, records a completed FPX transaction and
+    /// returns the callback Billplz would send.
+    pub fn pay_bill(&mut self, id: &str) -> Result<(BillResponse, Callback), Reply> {
+        let bill = match self.bills.iter_mut().find(|b| b.id == id) {
+            None => return Err(Reply::not_found("Bill")),
+            Some(b) if b.state != BillState::Due => {
+                return Err(Reply::invalid(&["Only due bills can be paid"]))
+            }
+            Some(b) => b,
+        };
+        let paid_at = now();
+        bill.paid = true;
+        bill.state = BillState::Paid;
+        bill.paid_amount = bill.amount;
+        bill.paid_at = Some(paid_at.clone());
+
+        let transaction = Transaction {
+            id: new_id(),
+            status: "completed".into(),
+            completed_at: Some(paid_at.clone()),
+            payment_channel: Some("FPX".into()),
+        };
+        self.transactions
+            .entry(bill.id.clone())
+            .or_default()
+            .push(transaction.clone());
+
+        let mut params = vec![
+            ("id".to_string(), bill.id.clone()),
+            ("collection_id".to_string(), bill.collection_id.clone()),
+            ("paid".to_string(), "true".to_string()),
+            ("state".to_string(), bill.state.as_str().to_string()),
+            ("amount".to_string(), bill.amount.cents().to_string()),
+            (
+                "paid_amount".to_string(),
+                bill.paid_amount.cents().to_string(),
+            ),
+            ("due_at".to_string(), bill.due_at.clone()),
+            ("email".to_string(), bill.email.clone()),
+            (
+                "mobile".to_string(),
+                bill.mobile.clone().unwrap_or_default(),
+            ),
+            ("name".to_string(), bill.name.clone()),
+            ("url".to_string(), bill.url.clone().unwrap_or_default()),
+            ("paid_at".to_string(), paid_at),
+            ("transaction_id".to_string(), transaction.id),
+            ("transaction_status".to_string(), transaction.status),
+        ];
+        params.retain(|(_, v)| !v.is_empty());
+
+        let callback = Callback {
+            url: bill.callback_url.clone(),
+            params,
+        };
+        Ok((bill.clone(), callback))
+    }
+
+    pub fn fpx_banks(&self) -> Reply {
+        let banks: Vec<Value> = known_banks(Some(&Environment::Staging))
+            .into_iter()
+            .map(|b| json!({ "name": b.bank_code, "active": b.active }))
+            .collect();
+        Reply::ok(json!({ "banks": banks }))
+    }
+
+    pub fn create_bank_verification(&mut self, body: Bank) -> Reply {
+        if let Some(existing) = self.verifications.iter().find(|v| v.acc_no == body.acc_no) {
+            return Reply::ok(existing);
+        }
+        if !is_payout_bank_code(&body.code, Some(&Environment::Staging)) {
+            return Reply::invalid(&["Bank code is invalid"]);
+        }
+        let verification = BankVerificationResponse {
+            name: body.name,
+            id_no: body.id_no,
+            acc_no: body.acc_no,
+            code: body.code,
+            organization: body.organization,
+            authorization_date: None,
+            status: BankVerificationStatus::Pending,
+        };
+        self.verifications.push(verification.clone());
+        Reply::ok(verification)
+    }
+
+    pub fn get_bank_verification(&self, acc_no: &str) -> Reply {
+        match self.verifications.iter().find(|v| v.acc_no == acc_no) {
+            Some(v) => Reply::ok(v),
+            None => Reply::not_found("Bank account"),
+        }
+    }
+
+    pub fn set_bank_verification_status(&mut self, acc_no: &str, update: StatusUpdate) -> Reply {
+        match self.verifications.iter_mut().find(|v| v.acc_no == acc_no) {
+            Some(v) => {
+                v.status = update.status.into();
+                if v.status == BankVerificationStatus::Verified {
+                    v.authorization_date = Some(now()[..10].to_string());
+                }
+                Reply::ok(&*v)
+            }
+            None => Reply::not_found("Bank account"),
+        }
+    }
+
+    pub fn create_payout_collection(&mut self, body: PayoutCollection) -> Reply {
+        if body.title.trim().is_empty() {
+            return Reply::invalid(&["Title can't be blank"]);
+        }
+        let collection = PayoutCollectionResponse {
+            id: new_id(),
+            title: body.title,
+            mass_payment_instructions_count: 0,
+            paid_amount: Money::ZERO,
+            status: PayoutCollectionStatus::Active,
+        };
+        self.payout_collections.push(collection.clone());
+        Reply::ok(collection)
+    }
+
+    pub fn get_payout_collection(&self, id: &str) -> Reply {
+        match self.payout_collections.iter().find(|c| c.id == id) {
+            Some(c) => Reply::ok(c),
+            None => Reply::not_found("Mass payment instruction collection"),
+        }
+    }
+
+    pub fn list_payout_collections(&self, page_no: u32) -> Reply {
+        let all = self.payout_collections.iter().cloned();
+        Reply::ok(json!({
+            "mass_payment_instruction_collections": page(all, page_no),
+            "page": page_no
+        }))
+    }
+
+    pub fn create_payout(&mut self, body: Payout) -> Reply {
+        let Some(collection) = self
+            .payout_collections
+            .iter_mut()
+            .find(|c| c.id == body.mass_payment_instruction_collection_id)
+        else {
+            return Reply::not_found("Mass payment instruction collection");
+        };
+        let mut errors = Vec::new();
+        if !is_payout_bank_code(&body.bank_code, Some(&Environment::Staging)) {
+            errors.push("Bank code is invalid");
+        }
+        if !body.total.is_positive() {
+            errors.push("Total must be greater than 0");
+        }
+        if !errors.is_empty() {
+            return Reply::invalid(&errors);
+        }
+
+        collection.mass_payment_instructions_count += 1;
+        let payout = PayoutResponse {
+            id: new_id(),
+            mass_payment_instruction_collection_id: body.mass_payment_instruction_collection_id,
+            bank_code: body.bank_code,
+            bank_account_number: body.bank_account_number,
+            identity_number: body.identity_number,
+            name: body.name,
+            description: body.description,
+            email: None,
+            notification: None,
+            recipient_notification: None,
+            total: body.total,
+            reference_id: None,
+            status: PayoutStatus::Enqueued,
+        };
+        self.payouts.push(payout.clone());
+        Reply::ok(payout)
+    }
+
+    pub fn get_payout(&self, id: &str) -> Reply {
+        match self.payouts.iter().find(|p| p.id == id) {
+            Some(p) => Reply::ok(p),
+            None => Reply::not_found("Mass payment instruction"),
+        }
+    }
+
+    /// Moves a payout to `status`; completing it adds its total to the
+    /// collection's `paid_amount`.
+    pub fn set_payout_status(&mut self, id: &str, update: StatusUpdate) -> Reply {
+        let Some(payout) = self.payouts.iter_mut().find(|p| p.id == id) else {
+            return Reply::not_found("Mass payment instruction");
+        };
+        let status = PayoutStatus::from(update.status);
+        if status == PayoutStatus::Completed && payout.status != PayoutStatus::Completed {
+            if let Some(collection) = self
+                .payout_collections
+                .iter_mut()
+                .find(|c| c.id == payout.mass_payment_instruction_collection_id)
+            {
+                collection.paid_amount = collection
+                    .paid_amount
+                    .checked_add(payout.total)
+                    .unwrap_or(collection.paid_amount);
+            }
+        }
+        payout.status = status;
+        Reply::ok(&*payout)
+    }
+}
@@ -0,0 +1,190 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode, Url};
+
+use crate::error::BillplzError;
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, BillplzError>> + Send + 'a>>;
+
+/// A fully built request, with auth and body already applied.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// The request body as UTF-8 text, if any.
+    pub fn body_text(&self) -> Option<String> {
+        self.body
+            .as_deref()
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Sends requests on behalf of `BillplzClient`.
+///
+/// Implement this to add tracing, auditing or to run without a network.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// The default transport, backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let resp = builder.send().await?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?.to_vec();
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(feature = "testing")]
+pub use mock::MockTransport;
+
+#[cfg(feature = "testing")]
+mod mock {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use reqwest::{Method, StatusCode};
+
+    use super::{HttpRequest, HttpResponse, Transport, TransportFuture};
+
+    struct Route {
+        method: Method,
+        path: String,
+        response: HttpResponse,
+    }
+
+    #[derive(Default)]
+    struct State {
+        routes: Vec<Route>,
+        queue: VecDeque<HttpResponse>,
+        requests: Vec<HttpRequest>,
+    }
+
+    /// An in-memory transport for unit tests.
+    ///
+    /// Responses registered with `on` are matched by method and path and reused;
+    /// responses pushed with `push_response` are returned once, in order.
+    /// Unmatched requests get a Billplz-style 404.
+    #[derive(Clone, Default)]
+    pub struct MockTransport {
+        state: Arc<Mutex<State>>,
+    }
+
+    impl MockTransport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Responds to every `method` request for `path` with the given status and body.
+        pub fn on(&self, method: Method, path: &str, status: u16, body: impl Into<String>) {
+            self.on_response(method, path, response(status, body));
+        }
+
+        pub fn on_response(&self, method: Method, path: &str, response: HttpResponse) {
+            self.state.lock().unwrap().routes.push(Route {
+                method,
+                path: path.to_string(),
+                response,
+            });
+        }
+
+        /// Queues a one-shot response, used when no route matches.
+        pub fn push_response(&self, status: u16, body: impl Into<String>) {
+            self.push(response(status, body));
+        }
+
+        pub fn push(&self, response: HttpResponse) {
+            self.state.lock().unwrap().queue.push_back(response);
+        }
+
+        /// Every request sent so far, in order.
+        pub fn requests(&self) -> Vec<HttpRequest> {
+            self.state.lock().unwrap().requests.clone()
+        }
+    }
+
+    fn response(status: u16, body: impl Into<String>) -> HttpResponse {
+        let status = StatusCode::from_u16(status).expect("valid HTTP status code");
+        HttpResponse::new(status, body.into())
+    }
+
+    impl Transport for MockTransport {
+        fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+            let mut state = self.state.lock().unwrap();
+            let routed = state
+                .routes
+                .iter()
+                .find(|r| r.method == request.method && r.path == request.url.path())
+                .map(|r| r.response.clone());
+            let resp = routed.or_else(|| state.queue.pop_front()).unwrap_or_else(|| {
+                response(
+                    404,
+                    format!(
+                        r#"{{"error":{{"type":"RecordNotFound","message":"no mock response for {} {}"}}}}"#,
+                        request.method,
+                        request.url.path()
+                    ),
+                )
+            });
+            state.requests.push(request);
+            Box::pin(async move { Ok(resp) })
+        }
+    }
+}
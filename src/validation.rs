@@ -0,0 +1,114 @@
+//! Client-side checks run by the create builders before a request is sent.
+
+use std::fmt;
+
+/// One field that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// The request field, e.g. `email` or `split_payments[1].email`.
+    pub field: String,
+    pub message: String,
+}
+
+/// Every field a builder rejected, in the order they were checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationError {
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationError {
+    /// Whether `field` is among the failures.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.errors.iter().any(|e| e.field == field)
+    }
+
+    pub(crate) fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub(crate) fn require(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.push(field, "is required");
+        }
+    }
+
+    pub(crate) fn max_chars(&mut self, field: &str, value: &str, max: usize) {
+        let len = value.chars().count();
+        if len > max {
+            self.push(
+                field,
+                format!("must be at most {} characters, got {}", max, len),
+            );
+        }
+    }
+
+    pub(crate) fn email(&mut self, field: &str, value: &str) {
+        if !is_email(value) {
+            self.push(field, format!("{:?} is not a valid email address", value));
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), ValidationError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, e) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{} {}", e.field, e.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A single `@` with a non-empty local part and a dotted domain, no spaces.
+pub(crate) fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain.contains('.')
+        && domain.split('.').all(|part| !part.is_empty())
+}
+
+/// Malaysian mobile number in international format: `60` then 8 to 10 digits,
+/// optionally prefixed with `+` as Billplz itself returns them.
+pub(crate) fn is_mobile(value: &str) -> bool {
+    let value = value.strip_prefix('+').unwrap_or(value);
+    value.starts_with("60")
+        && (10..=12).contains(&value.len())
+        && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A calendar date written as `YYYY-MM-DD`.
+pub(crate) fn is_date(value: &str) -> bool {
+    value.len() == 10 && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// A SWIFT code: 8 or 11 uppercase letters and digits.
+pub(crate) fn is_bank_code(value: &str) -> bool {
+    matches!(value.len(), 8 | 11)
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// 6 to 20 digits.
+pub(crate) fn is_account_number(value: &str) -> bool {
+    (6..=20).contains(&value.len()) && value.bytes().all(|b| b.is_ascii_digit())
+}
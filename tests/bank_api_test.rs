@@ -1,4 +1,7 @@
-use billplz::{BillplzClient, Environment};
+use std::time::Duration;
+
+use billplz::{BillplzClient, Environment, RetryPolicy};
+use billplz::models::bank::BankVerificationStatus;
 use wiremock::{MockServer, Mock, ResponseTemplate};
 use wiremock::matchers::{method, path};
 
@@ -10,6 +13,8 @@ fn test_get_fpx_banks_production() {
     assert!(banks.iter().any(|b| b.bank_code == "MB2U0227"));
     // No test banks in production
     assert!(!banks.iter().any(|b| b.bank_code.starts_with("TEST")));
+    let names: std::collections::HashSet<_> = banks.iter().map(|b| &b.bank_name).collect();
+    assert_eq!(names.len(), banks.len(), "duplicate bank names");
 }
 
 #[test]
@@ -23,7 +28,7 @@ fn test_get_fpx_banks_staging_includes_test_banks() {
 #[tokio::test]
 async fn test_get_bank_verification_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v3/bank_verification_services/999988887777"))
@@ -34,13 +39,14 @@ async fn test_get_bank_verification_success() {
         .await;
 
     let resp = client.get_bank_verification("999988887777").await.unwrap();
-    assert!(resp.contains("verified"));
+    assert_eq!(resp.status, BankVerificationStatus::Verified);
+    assert_eq!(resp.authorization_date.as_deref(), Some("2024-01-01"));
 }
 
 #[tokio::test]
 async fn test_create_bank_verification_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v3/bank_verification_services"))
@@ -56,5 +62,138 @@ async fn test_create_bank_verification_success() {
         .send()
         .await
         .unwrap();
-    assert!(resp.contains("pending"));
+    assert_eq!(resp.status, BankVerificationStatus::Pending);
+    assert!(resp.organization);
+}
+
+#[tokio::test]
+async fn test_get_bank_verification_api_error() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "bad-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bank_verification_services/999988887777"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "error": { "type": "Unauthorized", "message": "Invalid API key" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let err = client.get_bank_verification("999988887777").await.unwrap_err();
+    assert!(err.is_unauthorized(), "got {:?}", err);
+    assert_eq!(err.status(), Some(401));
+}
+
+fn fpx_banks_body() -> serde_json::Value {
+    serde_json::json!({
+        "banks": [
+            { "name": "MB2U0227", "active": true },
+            { "name": "BIMB0340", "active": false },
+            { "name": "NEWB0001", "active": true }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn test_fetch_fpx_banks_live() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/fpx_banks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fpx_banks_body()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let banks = client.fetch_fpx_banks().await.unwrap();
+    assert_eq!(banks.len(), 3);
+    assert_eq!(banks[0].bank_name, "Maybank2u");
+    assert!(!banks[1].active);
+    // Unknown codes are named after the code
+    assert_eq!(banks[2].bank_name, "NEWB0001");
+
+    // Served from the cache
+    let again = client.fetch_fpx_banks().await.unwrap();
+    assert_eq!(again.len(), 3);
+}
+
+#[tokio::test]
+async fn test_fetch_fpx_banks_refetches_after_ttl() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .fpx_bank_cache_ttl(Duration::ZERO)
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/fpx_banks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fpx_banks_body()))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    client.fetch_fpx_banks().await.unwrap();
+    client.fetch_fpx_banks().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_fpx_banks_falls_back_when_offline() {
+    let client = BillplzClient::builder()
+        .environment(Environment::Production)
+        .base_url("http://127.0.0.1:1")
+        .api_key("test-key")
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let banks = client.fetch_fpx_banks().await.unwrap();
+    assert_eq!(banks.len(), client.get_fpx_banks().len());
+}
+
+#[tokio::test]
+async fn test_fetch_fpx_banks_uses_stale_cache_on_server_error() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .fpx_bank_cache_ttl(Duration::ZERO)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/fpx_banks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(fpx_banks_body()))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/fpx_banks"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    client.fetch_fpx_banks().await.unwrap();
+    let banks = client.fetch_fpx_banks().await.unwrap();
+    assert!(banks.iter().any(|b| b.bank_code == "NEWB0001"));
+}
+
+#[tokio::test]
+async fn test_fetch_fpx_banks_propagates_auth_errors() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "bad-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/fpx_banks"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "error": { "type": "Unauthorized", "message": "Invalid API key" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let err = client.fetch_fpx_banks().await.unwrap_err();
+    assert!(err.is_unauthorized(), "got {:?}", err);
 }
@@ -1,11 +1,13 @@
-use billplz::BillplzClient;
+use billplz::models::bank::FpxBank;
+use billplz::models::bill::{AutoSubmit, BillResponse, CheckoutOptions};
+use billplz::{BillplzClient, BillplzError, Environment};
 use wiremock::{MockServer, Mock, ResponseTemplate};
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{body_partial_json, method, path, query_param};
 
 #[tokio::test]
 async fn test_get_bill_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v3/bills/bill123"))
@@ -35,7 +37,7 @@ async fn test_get_bill_success() {
 #[tokio::test]
 async fn test_create_bill_required_fields_only() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v3/bills"))
@@ -68,7 +70,7 @@ async fn test_create_bill_required_fields_only() {
 #[tokio::test]
 async fn test_create_bill_with_optional_fields() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v3/bills"))
@@ -103,3 +105,248 @@ async fn test_create_bill_with_optional_fields() {
         .unwrap();
     assert_eq!(resp.id, "opt_bill");
 }
+
+#[tokio::test]
+async fn test_delete_bill_success() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client.delete_bill("bill123").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_bill_transactions_with_filters() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123/transactions"))
+        .and(query_param("page", "2"))
+        .and(query_param("status", "completed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bill_id": "bill123",
+            "transactions": [
+                {
+                    "id": "60793D4707CD",
+                    "status": "completed",
+                    "completed_at": "2017-02-23T12:49:23.612+08:00",
+                    "payment_channel": "FPX"
+                }
+            ],
+            "page": 2
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .get_bill_transactions("bill123")
+        .page(2)
+        .status("completed")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.bill_id, "bill123");
+    assert_eq!(resp.page, 2);
+    assert_eq!(resp.transactions.len(), 1);
+    assert_eq!(resp.transactions[0].payment_channel.as_deref(), Some("FPX"));
+}
+
+fn bill_with_reference(label: Option<&str>, code: Option<&str>) -> BillResponse {
+    serde_json::from_value(serde_json::json!({
+        "id": "bp_bill",
+        "collection_id": "col1",
+        "email": "test@test.com",
+        "name": "Test User",
+        "amount": 10000,
+        "callback_url": "https://cb.url",
+        "description": "Test",
+        "due_at": "2024-07-12",
+        "paid": false,
+        "state": "due",
+        "reference_1_label": label,
+        "reference_1": code,
+        "url": "https://www.billplz.com/bills/bp_bill"
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_create_bill_with_preferred_bank() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .and(body_partial_json(serde_json::json!({
+            "reference_1_label": "Bank Code",
+            "reference_1": "MB2U0227"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "bp_bill",
+            "collection_id": "col1",
+            "email": "test@test.com",
+            "name": "Test User",
+            "amount": 10000,
+            "callback_url": "https://cb.url",
+            "description": "Test",
+            "due_at": "2024-07-12",
+            "paid": false,
+            "state": "due",
+            "reference_1_label": "Bank Code",
+            "reference_1": "MB2U0227",
+            "url": "https://www.billplz.com/bills/bp_bill"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let bill = client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Test", "2024-07-12")
+        .preferred_bank("MB2U0227")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bill.preferred_bank().unwrap().as_str(), "MB2U0227");
+
+    let url = bill
+        .checkout_url(
+            &CheckoutOptions::new()
+                .auto_submit(AutoSubmit::Enabled)
+                .environment(Environment::Production),
+        )
+        .unwrap();
+    assert_eq!(url, "https://www.billplz.com/bills/bp_bill?auto_submit=true");
+}
+
+#[tokio::test]
+async fn test_create_bill_with_auto_submit() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "bp_bill",
+            "collection_id": "col1",
+            "email": "test@test.com",
+            "name": "Test User",
+            "amount": 10000,
+            "callback_url": "https://cb.url",
+            "description": "Test",
+            "due_at": "2024-07-12",
+            "paid": false,
+            "state": "due",
+            "reference_1_label": "Bank Code",
+            "reference_1": "TEST0021",
+            "url": "https://www.billplz-sandbox.com/bills/bp_bill"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let bill = client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Test", "2024-07-12")
+        .preferred_bank("TEST0021")
+        .auto_submit(AutoSubmit::Enabled)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        bill.url.as_deref(),
+        Some("https://www.billplz-sandbox.com/bills/bp_bill?auto_submit=true")
+    );
+}
+
+#[tokio::test]
+async fn test_create_bill_auto_submit_checks_bank_for_environment() {
+    let client = BillplzClient::builder()
+        .environment(Environment::Production)
+        .api_key("test-key")
+        .build()
+        .unwrap();
+
+    let err = client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Test", "2024-07-12")
+        .preferred_bank("TEST0021")
+        .auto_submit(AutoSubmit::Enabled)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, BillplzError::InvalidRequest(e) if e.has_field("reference_1")), "got {:?}", err);
+
+    let err = client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Test", "2024-07-12")
+        .auto_submit(AutoSubmit::Enabled)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, BillplzError::InvalidRequest(e) if e.has_field("auto_submit")), "got {:?}", err);
+}
+
+#[test]
+fn test_checkout_url_without_auto_submit_is_bill_url() {
+    let bill = bill_with_reference(None, None);
+    let url = bill.checkout_url(&CheckoutOptions::new()).unwrap();
+    assert_eq!(url, "https://www.billplz.com/bills/bp_bill");
+}
+
+#[test]
+fn test_checkout_url_rejects_unknown_bank() {
+    let bill = bill_with_reference(Some("Bank Code"), Some("NOPE0001"));
+    let err = bill
+        .checkout_url(
+            &CheckoutOptions::new()
+                .auto_submit(AutoSubmit::Enabled)
+                .environment(Environment::Production),
+        )
+        .unwrap_err();
+    assert!(matches!(err, BillplzError::InvalidPayload(_)), "got {:?}", err);
+}
+
+#[test]
+fn test_checkout_url_uses_bank_list_for_environment() {
+    let bill = bill_with_reference(Some("Bank Code"), Some("TEST0021"));
+    let options = CheckoutOptions::new().auto_submit(AutoSubmit::Enabled);
+
+    assert!(bill.checkout_url(&options.clone().environment(Environment::Staging)).is_ok());
+    let err = bill
+        .checkout_url(&options.clone().environment(Environment::Production))
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown FPX bank"), "got {}", err);
+    // Without an environment or a bank list there is nothing to check against.
+    assert!(bill.checkout_url(&options).is_err());
+}
+
+#[test]
+fn test_checkout_url_requires_preferred_bank() {
+    let bill = bill_with_reference(Some("Ref 1"), Some("MB2U0227"));
+    assert!(bill.preferred_bank().is_none());
+    assert!(bill
+        .checkout_url(&CheckoutOptions::new().auto_submit(AutoSubmit::Enabled))
+        .is_err());
+}
+
+#[test]
+fn test_checkout_url_checks_live_bank_list() {
+    let bill = bill_with_reference(Some("Bank Code"), Some("MB2U0227"));
+    let banks = vec![FpxBank {
+        bank_code: "MB2U0227".into(),
+        bank_name: "Maybank2u".into(),
+        active: false,
+    }];
+    let err = bill
+        .checkout_url(
+            &CheckoutOptions::new()
+                .auto_submit(AutoSubmit::Enabled)
+                .banks(banks),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("not active"), "got {}", err);
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use billplz::{BillplzClient, BillplzError, BulkPayoutOptions, Environment, RetryPolicy};
+use futures::StreamExt;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json;
+
+#[tokio::test]
+async fn test_create_bills_bulk_reports_every_item() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .and(body_partial_json(serde_json::json!({ "name": "Bad" })))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "error": { "type": "RecordInvalid", "message": ["Email is invalid"] }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(4)
+        .mount(&mock_server)
+        .await;
+
+    let names = ["A", "B", "Bad", "C", "D"];
+    let bills = names.iter().map(|name| {
+        client.create_bill("col1", "test@test.com", *name, 10000, "https://cb.url", "Invoice", "2024-07-12")
+    });
+
+    let mut outcomes: Vec<_> = client.create_bills_bulk(bills, 2).collect().await;
+    outcomes.sort_by_key(|o| o.index);
+
+    assert_eq!(outcomes.len(), 5);
+    assert!(outcomes[0].result.is_ok());
+    assert!(matches!(outcomes[2].result, Err(BillplzError::Validation { .. })));
+    assert_eq!(outcomes.iter().filter(|o| o.result.is_ok()).count(), 4);
+}
+
+#[tokio::test]
+async fn test_create_bills_bulk_reads_input_lazily() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .mount(&mock_server)
+        .await;
+
+    let pulled = AtomicUsize::new(0);
+    let bills = (0..1000).map(|_| {
+        pulled.fetch_add(1, Ordering::SeqCst);
+        client.create_bill("col1", "test@test.com", "A", 10000, "https://cb.url", "Invoice", "2024-07-12")
+    });
+
+    let mut outcomes = Box::pin(client.create_bills_bulk(bills, 2));
+    assert!(outcomes.next().await.unwrap().result.is_ok());
+    assert!(pulled.load(Ordering::SeqCst) <= 3);
+}
+
+#[tokio::test]
+async fn test_create_bills_bulk_resends_after_rate_limit() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key").with_retry_policy(
+        RetryPolicy::default()
+            .max_attempts(3)
+            .base_delay(Duration::from_millis(1)),
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let bills = vec![client.create_bill("col1", "test@test.com", "A", 10000, "https://cb.url", "Invoice", "2024-07-12")];
+    let outcomes: Vec<_> = client.create_bills_bulk(bills, 1).collect().await;
+
+    assert_eq!(outcomes[0].result.as_ref().unwrap().id, "bill1");
+}
+
+#[tokio::test]
+async fn test_create_bills_bulk_gives_up_after_max_attempts() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key").with_retry_policy(
+        RetryPolicy::default()
+            .max_attempts(2)
+            .base_delay(Duration::from_millis(1)),
+    );
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let bills = vec![client.create_bill("col1", "test@test.com", "A", 10000, "https://cb.url", "Invoice", "2024-07-12")];
+    let outcomes: Vec<_> = client.create_bills_bulk(bills, 1).collect().await;
+
+    assert!(matches!(outcomes[0].result, Err(BillplzError::RateLimited { .. })));
+}
+
+fn payout_json(id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "mass_payment_instruction_collection_id": "pc1",
+        "bank_code": "MBBEMYKL",
+        "bank_account_number": "999988887777",
+        "identity_number": "91234567890",
+        "name": "Test User",
+        "description": "Salary",
+        "total": 50000,
+        "status": "enqueued"
+    })
+}
+
+fn verification_json(acc_no: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "name": "Test User",
+        "id_no": "91234567890",
+        "acc_no": acc_no,
+        "code": "MBBEMYKL",
+        "organization": false,
+        "status": status
+    })
+}
+
+#[tokio::test]
+async fn test_create_payouts_bulk_preflight_rejects_locally() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/mass_payment_instructions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_json("mpi1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let payouts = vec![
+        client.create_payout("pc1", "MBBEMYKL", "999988887777", "91234567890", "Good", "Salary", 50000),
+        client.create_payout("pc1", "NOT-A-BANK", "999988887777", "91234567890", "Bad bank", "Salary", 50000),
+        client.create_payout("pc1", "MBBEMYKL", "9999-8888", "91234567890", "Bad account", "Salary", 50000),
+        client.create_payout("pc1", "MBBEMYKL", "999988887777", "91234567890", "Zero", "Salary", 0),
+        client.create_payout("pc1", "MBBEMYKX", "999988887777", "91234567890", "Unknown bank", "Salary", 50000),
+        client.create_payout("pc1", "TEST0021", "999988887777", "91234567890", "Test bank", "Salary", 50000),
+    ];
+    let mut outcomes: Vec<_> = client
+        .create_payouts_bulk(payouts, BulkPayoutOptions::default())
+        .collect()
+        .await;
+    outcomes.sort_by_key(|o| o.index);
+
+    assert_eq!(outcomes[0].result.as_ref().unwrap().id, "mpi1");
+    let rejected = ["bank_code", "bank_account_number", "total", "bank_code", "bank_code"];
+    for (outcome, field) in outcomes[1..].iter().zip(rejected) {
+        assert!(
+            matches!(&outcome.result, Err(BillplzError::InvalidRequest(e)) if e.has_field(field)),
+            "got {:?}",
+            outcome.result
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_create_payouts_bulk_accepts_test_banks_on_staging() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .environment(Environment::Staging)
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .build()
+        .unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/mass_payment_instructions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_json("mpi1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let payouts = [client.create_payout("pc1", "TEST0021", "999988887777", "91234567890", "Test User", "Salary", 50000)];
+    let outcomes: Vec<_> = client
+        .create_payouts_bulk(payouts, BulkPayoutOptions::default())
+        .collect()
+        .await;
+
+    assert_eq!(outcomes[0].result.as_ref().unwrap().id, "mpi1");
+}
+
+#[tokio::test]
+async fn test_create_payouts_bulk_skips_unverified_accounts() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bank_verification_services/111122223333"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(verification_json("111122223333", "verified")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bank_verification_services/444455556666"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(verification_json("444455556666", "pending")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bank_verification_services/777788889999"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "type": "RecordNotFound", "message": "Not found" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v4/mass_payment_instructions"))
+        .and(body_partial_json(serde_json::json!({ "bank_account_number": "111122223333" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_json("mpi1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let payouts = ["111122223333", "444455556666", "777788889999"].map(|acc_no| {
+        client.create_payout("pc1", "MBBEMYKL", acc_no, "91234567890", "Test User", "Salary", 50000)
+    });
+    let mut outcomes: Vec<_> = client
+        .create_payouts_bulk(payouts, BulkPayoutOptions::default().require_verified(true))
+        .collect()
+        .await;
+    outcomes.sort_by_key(|o| o.index);
+
+    assert!(outcomes[0].result.is_ok());
+    assert!(matches!(
+        &outcomes[1].result,
+        Err(BillplzError::UnverifiedAccount { status, .. }) if status == "pending"
+    ));
+    assert!(matches!(
+        &outcomes[2].result,
+        Err(BillplzError::UnverifiedAccount { status, .. }) if status == "not found"
+    ));
+}
@@ -0,0 +1,124 @@
+use billplz::models::card::{Card, CardStatus, ChargeStatus};
+use billplz::{BillplzClient, BillplzError};
+use wiremock::matchers::{body_json, method, path, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_create_card_success() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/cards"))
+        .and(body_json(serde_json::json!({
+            "name": "Ali",
+            "email": "ali@example.com",
+            "phone": "60123456789",
+            "card_number": "4111111111111111",
+            "cvv": "123",
+            "expiry": "12/30"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "card1",
+            "card_number": "1111",
+            "provider": "visa",
+            "token": "tok_abc",
+            "status": "active"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let card = client
+        .create_card("Ali", "ali@example.com", "60123456789", "4111111111111111", "123", "12/30")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(card.id, "card1");
+    assert_eq!(card.token, "tok_abc");
+    assert_eq!(card.status, CardStatus::Active);
+}
+
+#[tokio::test]
+async fn test_delete_card_sends_token() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v4/cards/card1"))
+        .and(query_param_is_missing("token"))
+        .and(body_json(serde_json::json!({ "token": "tok_abc" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "card1",
+            "card_number": "1111",
+            "provider": "visa",
+            "token": "tok_abc",
+            "status": "deleted"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let card = client.delete_card("card1", "tok_abc").await.unwrap();
+    assert_eq!(card.status, CardStatus::Deleted);
+}
+
+#[tokio::test]
+async fn test_charge_bill_with_token() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/bills/bill1/charge"))
+        .and(body_json(serde_json::json!({ "card_id": "card1", "token": "tok_abc" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amount": 2990,
+            "status": "success",
+            "reference_id": "ref-123",
+            "bill_id": "bill1"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let charge = client
+        .charge_bill_with_token("bill1", "card1", "tok_abc")
+        .await
+        .unwrap();
+    assert_eq!(charge.status, ChargeStatus::Success);
+    assert_eq!(charge.amount, 2990);
+}
+
+#[tokio::test]
+async fn test_charge_bill_with_invalid_token() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/bills/bill1/charge"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "error": { "type": "RecordInvalid", "message": ["Token is invalid"] }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let err = client
+        .charge_bill_with_token("bill1", "card1", "bad")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BillplzError::Validation { .. }), "got {:?}", err);
+}
+
+#[test]
+fn test_card_debug_masks_secrets() {
+    let card = Card {
+        name: "Ali".into(),
+        email: "ali@example.com".into(),
+        phone: "60123456789".into(),
+        card_number: "4111111111111111".into(),
+        cvv: "123".into(),
+        expiry: "12/30".into(),
+    };
+    let debug = format!("{:?}", card);
+    assert!(debug.contains("****1111"));
+    assert!(!debug.contains("4111111111111111"));
+    assert!(!debug.contains("123\""));
+}
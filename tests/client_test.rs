@@ -1,4 +1,4 @@
-use billplz::{BillplzClient, Environment};
+use billplz::{BillplzClient, BillplzError, Environment};
 
 #[test]
 fn test_client_production_url() {
@@ -17,3 +17,108 @@ fn test_client_custom_url() {
     let client = BillplzClient::with_base_url("http://localhost:8080", "test-key");
     assert_eq!(client.base_url(), "http://localhost:8080");
 }
+
+#[tokio::test]
+async fn test_client_custom_url_invalid_fails_requests() {
+    let client = BillplzClient::with_base_url("not a url", "test-key");
+    let err = client.get_bill("bill1").await.unwrap_err();
+    assert!(matches!(err, BillplzError::Http(_)));
+}
+
+#[test]
+fn test_builder_with_environment() {
+    let client = BillplzClient::builder()
+        .environment(Environment::Staging)
+        .api_key("test-key")
+        .timeout(std::time::Duration::from_secs(30))
+        .connect_timeout(std::time::Duration::from_secs(5))
+        .user_agent("my-app/1.0")
+        .build()
+        .unwrap();
+    assert_eq!(client.base_url(), "https://www.billplz-sandbox.com");
+}
+
+#[test]
+fn test_builder_base_url_trailing_slash_trimmed() {
+    let client = BillplzClient::builder()
+        .base_url("http://localhost:8080/")
+        .api_key("test-key")
+        .build()
+        .unwrap();
+    assert_eq!(client.base_url(), "http://localhost:8080");
+}
+
+#[test]
+fn test_builder_rejects_invalid_base_url() {
+    let err = BillplzClient::builder()
+        .base_url("not a url")
+        .api_key("test-key")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+
+    let err = BillplzClient::builder()
+        .base_url("ftp://example.com")
+        .api_key("test-key")
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+}
+
+#[test]
+fn test_builder_requires_api_key_and_target() {
+    let err = BillplzClient::builder()
+        .environment(Environment::Production)
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+
+    let err = BillplzClient::builder().api_key("key").build().err().unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+}
+
+#[test]
+fn test_builder_rejects_http_client_with_timeout() {
+    let err = BillplzClient::builder()
+        .environment(Environment::Production)
+        .api_key("key")
+        .http_client(reqwest::Client::new())
+        .timeout(std::time::Duration::from_secs(1))
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+}
+
+#[tokio::test]
+async fn test_builder_sends_user_agent_and_custom_headers() {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .user_agent("my-app/1.0")
+        .header("X-Request-Source", "batch")
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/collections/col1"))
+        .and(header("user-agent", "my-app/1.0"))
+        .and(header("x-request-source", "batch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "col1",
+            "title": "Collection",
+            "status": "active"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client.get_collection("col1").await.unwrap();
+}
@@ -1,11 +1,11 @@
 use billplz::{BillplzClient, BillplzError};
 use wiremock::{MockServer, Mock, ResponseTemplate};
-use wiremock::matchers::{method, path, header};
+use wiremock::matchers::{method, path, header, query_param};
 
 #[tokio::test]
 async fn test_get_collection_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-api-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v4/collections/col123"))
@@ -31,7 +31,7 @@ async fn test_get_collection_success() {
 #[tokio::test]
 async fn test_get_collection_api_error() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "bad-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "bad-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v4/collections/col123"))
@@ -46,18 +46,20 @@ async fn test_get_collection_api_error() {
 
     let err = client.get_collection("col123").await.unwrap_err();
     match err {
-        BillplzError::Api { error_type, message } => {
-            assert_eq!(error_type, "unauthorized");
+        BillplzError::Unauthorized { message, context } => {
             assert_eq!(message, "Invalid API key");
+            assert_eq!(context.status, 401);
+            assert_eq!(context.method, "GET");
+            assert_eq!(context.path, "/api/v4/collections/col123");
         }
-        _ => panic!("Expected Api error, got {:?}", err),
+        _ => panic!("Expected Unauthorized error, got {:?}", err),
     }
 }
 
 #[tokio::test]
 async fn test_create_collection_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-api-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v4/collections"))
@@ -82,7 +84,7 @@ async fn test_create_collection_success() {
 #[tokio::test]
 async fn test_create_collection_with_split_payments() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-api-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v4/collections"))
@@ -105,3 +107,56 @@ async fn test_create_collection_with_split_payments() {
         .unwrap();
     assert_eq!(resp.id, "split_col");
 }
+
+#[tokio::test]
+async fn test_list_collections_with_filters() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/collections"))
+        .and(query_param("page", "2"))
+        .and(query_param("status", "active"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "collections": [
+                { "id": "col1", "title": "First", "status": "active" },
+                { "id": "col2", "title": "Second", "status": "active" }
+            ],
+            "page": 2
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .list_collections()
+        .page(2)
+        .status("active")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.page, 2);
+    assert_eq!(resp.collections.len(), 2);
+    assert_eq!(resp.collections[1].id, "col2");
+}
+
+#[tokio::test]
+async fn test_activate_and_deactivate_collection() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/collections/col123/activate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/collections/col123/deactivate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client.activate_collection("col123").await.unwrap();
+    client.deactivate_collection("col123").await.unwrap();
+}
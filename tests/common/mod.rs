@@ -0,0 +1,57 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+/// A due, unpaid bill as returned by GET/POST /api/v3/bills.
+pub fn bill_json(id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "collection_id": "col1",
+        "email": "test@test.com",
+        "name": "Test User",
+        "amount": 10000,
+        "callback_url": "https://cb.url",
+        "description": "Invoice",
+        "due_at": "2024-07-12",
+        "paid": false,
+        "state": "due",
+        "paid_amount": 0,
+        "url": format!("https://www.billplz.com/bills/{}", id)
+    })
+}
+
+/// `bill_json(id)` with some fields replaced, e.g. `{ "state": "paid" }`.
+pub fn bill_json_with(id: &str, fields: serde_json::Value) -> serde_json::Value {
+    let mut bill = bill_json(id);
+    let (Some(bill_fields), serde_json::Value::Object(fields)) = (bill.as_object_mut(), fields)
+    else {
+        panic!("bill fields must be a JSON object");
+    };
+    bill_fields.extend(fields);
+    bill
+}
+
+/// X-Signature key and signature of the callback in the Billplz docs.
+pub const KEY: &str = "S-s7b4ATnEc4PhAx0kL1d3Qg";
+pub const CALLBACK_SIGNATURE: &str =
+    "1db5f811965190b061994aabbe39c46dfab15d7a4350778ce3d8a520e80d7aa7";
+
+/// The Billplz docs' example callback, form-encoded, signed with `signature`.
+pub fn callback_form(signature: &str) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs([
+            ("id", "W_79pJDk"),
+            ("collection_id", "599"),
+            ("paid", "true"),
+            ("state", "paid"),
+            ("amount", "200"),
+            ("paid_amount", "200"),
+            ("due_at", "2020-12-31"),
+            ("email", "api@billplz.com"),
+            ("mobile", "+60112223333"),
+            ("name", "MICHAEL API"),
+            ("url", "http://www.billplz.com/bills/W_79pJDk"),
+            ("paid_at", "2015-03-09 16:23:59 +0800"),
+            ("x_signature", signature),
+        ])
+        .finish()
+}
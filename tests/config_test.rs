@@ -1,11 +1,19 @@
+mod common;
+
 use std::io::Write;
 
+use billplz::cli::config::Config;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json;
+
 #[test]
 fn test_config_from_env_vars() {
     std::env::set_var("BILLPLZ_API_KEY", "env-test-key");
     std::env::set_var("BILLPLZ_ENVIRONMENT", "staging");
 
-    let config = billplz::cli::config::Config::load(None).unwrap();
+    let config = Config::load(None).unwrap();
     assert_eq!(config.api_key, "env-test-key");
     assert_eq!(config.environment, "staging");
 
@@ -24,7 +32,7 @@ fn test_config_from_file() {
     writeln!(f, r#"api_key = "file-test-key""#).unwrap();
     writeln!(f, r#"environment = "production""#).unwrap();
 
-    let config = billplz::cli::config::Config::load(Some(&config_path)).unwrap();
+    let config = Config::load(Some(&config_path)).unwrap();
     assert_eq!(config.api_key, "file-test-key");
     assert_eq!(config.environment, "production");
 }
@@ -40,7 +48,7 @@ fn test_env_vars_override_config_file() {
     writeln!(f, r#"api_key = "file-key""#).unwrap();
     writeln!(f, r#"environment = "production""#).unwrap();
 
-    let config = billplz::cli::config::Config::load(Some(&config_path)).unwrap();
+    let config = Config::load(Some(&config_path)).unwrap();
     assert_eq!(config.api_key, "env-key");
     assert_eq!(config.environment, "staging");
 
@@ -53,6 +61,115 @@ fn test_config_missing_api_key_errors() {
     std::env::remove_var("BILLPLZ_API_KEY");
     std::env::remove_var("BILLPLZ_ENVIRONMENT");
 
-    let result = billplz::cli::config::Config::load(Some(std::path::Path::new("/nonexistent")));
+    let result = Config::load(Some(std::path::Path::new("/nonexistent")));
     assert!(result.is_err());
 }
+
+#[test]
+fn test_config_x_signature_key_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    let mut f = std::fs::File::create(&config_path).unwrap();
+    writeln!(f, r#"api_key = "file-key""#).unwrap();
+    writeln!(f, r#"x_signature_key = "S-file-key""#).unwrap();
+
+    let config = Config::load(Some(&config_path)).unwrap();
+    assert_eq!(config.x_signature_key.as_deref(), Some("S-file-key"));
+}
+
+#[test]
+fn test_config_idempotency_store_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    let mut f = std::fs::File::create(&config_path).unwrap();
+    writeln!(f, r#"api_key = "file-key""#).unwrap();
+    writeln!(f, r#"idempotency_store = "/tmp/billplz-keys.json""#).unwrap();
+
+    let config = Config::load(Some(&config_path)).unwrap();
+    assert_eq!(
+        config.idempotency_store.as_deref(),
+        Some(std::path::Path::new("/tmp/billplz-keys.json"))
+    );
+}
+
+#[test]
+fn test_config_base_url_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    let mut f = std::fs::File::create(&config_path).unwrap();
+    writeln!(f, r#"api_key = "file-key""#).unwrap();
+    writeln!(f, r#"base_url = "http://127.0.0.1:8090""#).unwrap();
+
+    let config = Config::load(Some(&config_path)).unwrap();
+    assert_eq!(config.base_url.as_deref(), Some("http://127.0.0.1:8090"));
+    assert_eq!(config.into_client().unwrap().base_url(), "http://127.0.0.1:8090");
+}
+
+#[test]
+fn test_config_invalid_base_url_is_config_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    let mut f = std::fs::File::create(&config_path).unwrap();
+    writeln!(f, r#"api_key = "file-key""#).unwrap();
+    writeln!(f, r#"base_url = "127.0.0.1:8090""#).unwrap();
+
+    let config = Config::load(Some(&config_path)).unwrap();
+    let err = config.into_client().err().unwrap();
+    assert!(matches!(err, billplz::BillplzError::Config(_)));
+}
+
+fn store_config(store: &std::path::Path, base_url: Option<String>, api_key: &str) -> Config {
+    Config {
+        api_key: api_key.into(),
+        environment: "staging".into(),
+        x_signature_key: None,
+        idempotency_store: Some(store.to_path_buf()),
+        base_url,
+    }
+}
+
+#[test]
+fn test_idempotency_store_path_is_namespaced() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("keys.json");
+    let path = |base_url: Option<&str>, api_key| {
+        store_config(&store, base_url.map(String::from), api_key)
+            .idempotency_store_path()
+            .unwrap()
+    };
+
+    let staging = path(None, "key-a");
+    assert_eq!(staging.parent(), Some(dir.path()));
+    assert!(staging.to_string_lossy().ends_with(".json"));
+    assert_eq!(staging, path(Some("https://www.billplz-sandbox.com/"), "key-a"));
+    assert_ne!(staging, path(Some("https://www.billplz.com"), "key-a"));
+    assert_ne!(staging, path(None, "key-b"));
+}
+
+#[tokio::test]
+async fn test_same_idempotency_key_on_two_base_urls_does_not_collide() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("keys.json");
+    let servers = [MockServer::start().await, MockServer::start().await];
+    for (server, id) in servers.iter().zip(["bill_a", "bill_b"]) {
+        Mock::given(method("POST"))
+            .and(path("/api/v3/bills"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(bill_json(id)))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    for (server, id) in servers.iter().zip(["bill_a", "bill_b"]) {
+        let client = store_config(&store, Some(server.uri()), "test-key")
+            .into_client()
+            .unwrap();
+        let bill = client
+            .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Invoice", "2024-07-12")
+            .idempotency_key("inv-42")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(bill.id, id);
+    }
+}
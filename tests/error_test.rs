@@ -1,15 +1,18 @@
-use billplz::BillplzError;
+use std::time::Duration;
+
+use billplz::error::MAX_ERROR_BODY_LEN;
+use billplz::{BillplzClient, BillplzError, ErrorContext, RetryPolicy};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test]
 fn test_api_error_display() {
     let err = BillplzError::Api {
         error_type: "unauthorized".to_string(),
         message: "Invalid API key".to_string(),
+        context: Box::new(ErrorContext::new(401, "GET", "/api/v3/bills/abc", "")),
     };
-    assert_eq!(
-        err.to_string(),
-        "API error (unauthorized): Invalid API key"
-    );
+    assert_eq!(err.to_string(), "API error (unauthorized): Invalid API key");
 }
 
 #[test]
@@ -17,8 +20,122 @@ fn test_api_error_debug() {
     let err = BillplzError::Api {
         error_type: "not_found".to_string(),
         message: "Bill not found".to_string(),
+        context: Box::new(ErrorContext::new(404, "GET", "/api/v3/bills/abc", "")),
     };
     let debug = format!("{:?}", err);
     assert!(debug.contains("not_found"));
     assert!(debug.contains("Bill not found"));
 }
+
+#[test]
+fn test_error_context_truncates_body() {
+    let body = "x".repeat(MAX_ERROR_BODY_LEN + 100);
+    let context = ErrorContext::new(500, "GET", "/", &body);
+    assert_eq!(context.body.len(), MAX_ERROR_BODY_LEN + 3);
+    assert!(context.body.ends_with("..."));
+}
+
+#[test]
+fn test_error_helpers() {
+    let not_found = BillplzError::NotFound {
+        message: "Bill not found".to_string(),
+        context: Box::new(ErrorContext::new(404, "GET", "/api/v3/bills/abc", "")),
+    };
+    assert!(not_found.is_not_found());
+    assert!(!not_found.is_retryable());
+    assert_eq!(not_found.status(), Some(404));
+
+    let rate_limited = BillplzError::RateLimited {
+        retry_after: Some(Duration::from_secs(30)),
+        context: Box::new(ErrorContext::new(429, "GET", "/api/v3/bills/abc", "")),
+    };
+    assert!(rate_limited.is_retryable());
+    assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(30)));
+    assert_eq!(rate_limited.to_string(), "Rate limited, retry after 30s");
+
+    assert!(BillplzError::Config("bad".into()).status().is_none());
+}
+
+#[tokio::test]
+async fn test_non_json_server_error_keeps_status_and_body() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(RetryPolicy::none());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/abc"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("<html>Bad Gateway</html>"))
+        .mount(&mock_server)
+        .await;
+
+    let err = client.get_bill("abc").await.unwrap_err();
+    match &err {
+        BillplzError::Api {
+            message, context, ..
+        } => {
+            assert_eq!(message, "Bad Gateway");
+            assert_eq!(context.status, 502);
+            assert_eq!(context.method, "GET");
+            assert_eq!(context.path, "/api/v3/bills/abc");
+            assert_eq!(context.body, "<html>Bad Gateway</html>");
+        }
+        _ => panic!("Expected Api error, got {:?}", err),
+    }
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_validation_error_collects_messages() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "error": {
+                "type": "RecordInvalid",
+                "message": ["Email is invalid", "Amount must be greater than 0"]
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let err = client
+        .create_bill("col", "nope", "Ali", 0, "http://cb", "desc", "2026-12-31")
+        .validate_on_send(false)
+        .send()
+        .await
+        .unwrap_err();
+    match err {
+        BillplzError::Validation { messages, context } => {
+            assert_eq!(
+                messages,
+                vec!["Email is invalid", "Amount must be greater than 0"]
+            );
+            assert_eq!(context.method, "POST");
+        }
+        _ => panic!("Expected Validation error, got {:?}", err),
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_reads_retry_after() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(RetryPolicy::none());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/abc"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "7"))
+        .mount(&mock_server)
+        .await;
+
+    let err = client.get_bill("abc").await.unwrap_err();
+    assert!(
+        matches!(err, BillplzError::RateLimited { .. }),
+        "got {:?}",
+        err
+    );
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(7)));
+    assert_eq!(err.status(), Some(429));
+}
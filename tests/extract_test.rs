@@ -0,0 +1,198 @@
+mod common;
+
+use billplz::extract::{VerifiedCallback, VerifiedRedirect};
+use billplz::XSignature;
+use common::{callback_form, CALLBACK_SIGNATURE, KEY};
+
+const REDIRECT_SIGNATURE: &str = "eff3db985f56bcedd98de1d8917ae9e0db5013ca087dc96c271e7e0021c9cd70";
+
+fn redirect_query(signature: &str) -> String {
+    format!(
+        "billplz%5Bid%5D=W_79pJDk&billplz%5Bpaid%5D=true&billplz%5Bpaid_at%5D=2015-03-09+16%3A23%3A59+%2B0800&billplz%5Bx_signature%5D={}",
+        signature
+    )
+}
+
+mod axum_extractors {
+    use super::*;
+    use axum::routing::{get, post};
+    use axum::Router;
+
+    async fn callback(VerifiedCallback(payload): VerifiedCallback) -> String {
+        payload.id
+    }
+
+    async fn redirect(VerifiedRedirect(payload): VerifiedRedirect) -> String {
+        payload.paid.to_string()
+    }
+
+    async fn spawn() -> String {
+        let app = Router::new()
+            .route("/callback", post(callback))
+            .route("/redirect", get(redirect))
+            .with_state(XSignature::new(KEY));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    async fn post_callback(url: &str, body: String) -> reqwest::Response {
+        reqwest::Client::new()
+            .post(format!("{}/callback", url))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verified_callback() {
+        let url = spawn().await;
+
+        let resp = post_callback(&url, callback_form(CALLBACK_SIGNATURE)).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.text().await.unwrap(), "W_79pJDk");
+    }
+
+    #[tokio::test]
+    async fn test_tampered_callback_is_unauthorized() {
+        let url = spawn().await;
+        let body = callback_form(CALLBACK_SIGNATURE).replace("amount=200", "amount=1");
+
+        let resp = post_callback(&url, body).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_callback_missing_signature_is_unauthorized() {
+        let url = spawn().await;
+
+        let resp = post_callback(&url, "id=W_79pJDk".to_string()).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_callback_is_bad_request() {
+        let url = spawn().await;
+
+        let resp = reqwest::Client::new()
+            .post(format!("{}/callback", url))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(vec![0xff, 0xfe])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_verified_redirect() {
+        let url = spawn().await;
+
+        let resp = reqwest::get(format!("{}/redirect?{}", url, redirect_query(REDIRECT_SIGNATURE)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.text().await.unwrap(), "true");
+    }
+
+    #[tokio::test]
+    async fn test_tampered_redirect_is_unauthorized() {
+        let url = spawn().await;
+        let query = redirect_query(REDIRECT_SIGNATURE).replace("paid%5D=true", "paid%5D=false");
+
+        let resp = reqwest::get(format!("{}/redirect?{}", url, query)).await.unwrap();
+        assert_eq!(resp.status(), 401);
+    }
+}
+
+mod actix_extractors {
+    use super::*;
+    use actix_web::{test, web, App};
+
+    async fn callback(VerifiedCallback(payload): VerifiedCallback) -> String {
+        payload.id
+    }
+
+    async fn redirect(VerifiedRedirect(payload): VerifiedRedirect) -> String {
+        payload.paid.to_string()
+    }
+
+    macro_rules! app {
+        ($data:expr) => {
+            test::init_service(
+                App::new()
+                    .app_data($data)
+                    .route("/callback", web::post().to(callback))
+                    .route("/redirect", web::get().to(redirect)),
+            )
+            .await
+        };
+    }
+
+    #[actix_web::test]
+    async fn test_verified_callback() {
+        let app = app!(web::Data::new(XSignature::new(KEY)));
+        let req = test::TestRequest::post()
+            .uri("/callback")
+            .insert_header(("content-type", "application/x-www-form-urlencoded"))
+            .set_payload(callback_form(CALLBACK_SIGNATURE))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, "W_79pJDk");
+    }
+
+    #[actix_web::test]
+    async fn test_tampered_callback_is_unauthorized() {
+        let app = app!(XSignature::new(KEY));
+        let req = test::TestRequest::post()
+            .uri("/callback")
+            .set_payload(callback_form(CALLBACK_SIGNATURE).replace("amount=200", "amount=1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[actix_web::test]
+    async fn test_callback_missing_signature_is_unauthorized() {
+        let app = app!(XSignature::new(KEY));
+        let body = callback_form(CALLBACK_SIGNATURE).replace("x_signature=", "signature=");
+        let req = test::TestRequest::post()
+            .uri("/callback")
+            .set_payload(body)
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[actix_web::test]
+    async fn test_verified_redirect() {
+        let app = app!(XSignature::new(KEY));
+        let req = test::TestRequest::get()
+            .uri(&format!("/redirect?{}", redirect_query(REDIRECT_SIGNATURE)))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, "true");
+    }
+
+    #[actix_web::test]
+    async fn test_missing_key_is_server_error() {
+        let app = app!(());
+        let req = test::TestRequest::get()
+            .uri(&format!("/redirect?{}", redirect_query(REDIRECT_SIGNATURE)))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 500);
+    }
+}
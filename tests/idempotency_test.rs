@@ -0,0 +1,308 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use billplz::{
+    BillplzClient, BillplzError, FileIdempotencyStore, IdempotencyField, IdempotencyRecord,
+    IdempotencyStore, MemoryIdempotencyStore, RetryPolicy, SqliteIdempotencyStore,
+};
+use billplz::models::bill::BillResponse;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json;
+
+async fn create(client: &BillplzClient, key: &str) -> Result<BillResponse, billplz::BillplzError> {
+    client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Invoice 42", "2024-07-12")
+        .idempotency_key(key)
+        .send()
+        .await
+}
+
+#[tokio::test]
+async fn test_repeated_send_returns_recorded_bill() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .and(body_partial_json(serde_json::json!({ "reference_2": "inv-42" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let first = create(&client, "inv-42").await.unwrap();
+    let second = create(&client, "inv-42").await.unwrap();
+    assert_eq!(first.id, "bill1");
+    assert_eq!(second.id, "bill1");
+}
+
+#[tokio::test]
+async fn test_different_keys_create_separate_bills() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    create(&client, "inv-42").await.unwrap();
+    create(&client, "inv-43").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_idempotency_field_reference_1() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .and(body_partial_json(serde_json::json!({ "reference_1": "inv-42" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client
+        .create_bill("col1", "test@test.com", "Test User", 10000, "https://cb.url", "Invoice 42", "2024-07-12")
+        .idempotency_key("inv-42")
+        .idempotency_field(IdempotencyField::Reference1)
+        .send()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_rejected_send_releases_key() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "error": { "type": "RecordInvalid", "message": ["Email is invalid"] }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill2")))
+        .mount(&mock_server)
+        .await;
+
+    // Billplz answered 422, so no bill exists and the key can be reused.
+    assert!(create(&client, "inv-42").await.is_err());
+    assert_eq!(create(&client, "inv-42").await.unwrap().id, "bill2");
+}
+
+#[tokio::test]
+async fn test_unanswered_send_is_not_posted_again() {
+    let mock_server = MockServer::start().await;
+    let store = Arc::new(MemoryIdempotencyStore::new());
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .with_idempotency_store(SharedStore(store.clone()));
+
+    // Billplz creates the bill, but the answer arrives after the client gave up.
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(bill_json("bill1"))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    assert!(matches!(
+        create(&client, "inv-42").await.unwrap_err(),
+        BillplzError::Http(_)
+    ));
+    assert!(matches!(
+        create(&client, "inv-42").await.unwrap_err(),
+        BillplzError::OutcomeUnknown { key } if key == "inv-42"
+    ));
+    assert!(matches!(
+        store.get("inv-42").unwrap(),
+        Some(IdempotencyRecord::Pending)
+    ));
+}
+
+#[tokio::test]
+async fn test_server_error_leaves_key_pending_until_released() {
+    let mock_server = MockServer::start().await;
+    let store = Arc::new(MemoryIdempotencyStore::new());
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .with_idempotency_store(SharedStore(store.clone()));
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill2")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    assert!(create(&client, "inv-42").await.is_err());
+    assert!(matches!(
+        create(&client, "inv-42").await.unwrap_err(),
+        BillplzError::OutcomeUnknown { .. }
+    ));
+
+    // Checked on Billplz: nothing was created, so the key may be reused.
+    store.release("inv-42").unwrap();
+    assert_eq!(create(&client, "inv-42").await.unwrap().id, "bill2");
+}
+
+#[tokio::test]
+async fn test_concurrent_sends_with_same_key_post_once() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(bill_json("bill1"))
+                .set_delay(Duration::from_millis(100)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let (first, second) = tokio::join!(create(&client, "inv-42"), create(&client, "inv-42"));
+    assert_eq!(first.unwrap().id, "bill1");
+    assert_eq!(second.unwrap().id, "bill1");
+}
+
+/// Lets a test keep a handle on the store it gives the client.
+struct SharedStore(Arc<MemoryIdempotencyStore>);
+
+impl IdempotencyStore for SharedStore {
+    fn get(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        self.0.get(key)
+    }
+
+    fn begin(&self, key: &str) -> Result<Option<IdempotencyRecord>, BillplzError> {
+        self.0.begin(key)
+    }
+
+    fn complete(&self, key: &str, bill: &BillResponse) -> Result<(), BillplzError> {
+        self.0.complete(key, bill)
+    }
+
+    fn release(&self, key: &str) -> Result<(), BillplzError> {
+        self.0.release(key)
+    }
+}
+
+#[tokio::test]
+async fn test_file_store_survives_new_client() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store_path = dir.path().join("keys").join("idempotency.json");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    for _ in 0..2 {
+        let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+            .with_idempotency_store(FileIdempotencyStore::new(&store_path));
+        assert_eq!(create(&client, "inv-42").await.unwrap().id, "bill1");
+    }
+}
+
+fn completed_id(record: Option<IdempotencyRecord>) -> Option<String> {
+    match record {
+        Some(IdempotencyRecord::Completed(bill)) => Some(bill.id),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_memory_store_round_trip() {
+    let store = MemoryIdempotencyStore::new();
+    let bill: BillResponse = serde_json::from_value(bill_json("bill1")).unwrap();
+
+    assert!(store.get("inv-42").unwrap().is_none());
+    assert!(store.begin("inv-42").unwrap().is_none());
+    assert!(matches!(
+        store.begin("inv-42").unwrap(),
+        Some(IdempotencyRecord::Pending)
+    ));
+    store.complete("inv-42", &bill).unwrap();
+    assert_eq!(
+        completed_id(store.get("inv-42").unwrap()).as_deref(),
+        Some("bill1")
+    );
+    store.release("inv-42").unwrap();
+    assert!(store.get("inv-42").unwrap().is_none());
+}
+
+#[test]
+fn test_file_store_keeps_pending_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("idempotency.json");
+
+    assert!(FileIdempotencyStore::new(&path)
+        .begin("inv-42")
+        .unwrap()
+        .is_none());
+    let store = FileIdempotencyStore::new(&path);
+    assert!(matches!(
+        store.get("inv-42").unwrap(),
+        Some(IdempotencyRecord::Pending)
+    ));
+}
+
+#[test]
+fn test_sqlite_store_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("idempotency.db");
+    let bill: BillResponse = serde_json::from_value(bill_json("bill1")).unwrap();
+
+    let store = SqliteIdempotencyStore::open(&db).unwrap();
+    assert!(store.begin("inv-42").unwrap().is_none());
+    assert!(store.begin("inv-43").unwrap().is_none());
+    store.complete("inv-42", &bill).unwrap();
+
+    let store = SqliteIdempotencyStore::open(&db).unwrap();
+    assert_eq!(
+        completed_id(store.begin("inv-42").unwrap()).as_deref(),
+        Some("bill1")
+    );
+    assert!(matches!(
+        store.get("inv-43").unwrap(),
+        Some(IdempotencyRecord::Pending)
+    ));
+    store.release("inv-43").unwrap();
+    assert!(store.get("inv-43").unwrap().is_none());
+}
@@ -0,0 +1,320 @@
+mod common;
+
+use std::io::Write;
+use std::time::Duration;
+
+use billplz::cli::import::{
+    read_rows, BillImport, BillRow, PayoutImport, PayoutRowResult, RowResult, RowStatus,
+};
+use billplz::BillplzClient;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json;
+
+const CSV: &str = "\
+email,name,amount,description,due_at,mobile
+a@test.com,Alice,10000,Invoice 1,2024-07-12,
+b@test.com,Bob,20000,Invoice 2,2024-07-12,+60123456789
+c@test.com,Carol,30000,Invoice 3,2024-07-12,
+";
+
+fn write_file(dir: &std::path::Path, name: &str, contents: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(contents.as_bytes())
+        .unwrap();
+    path
+}
+
+fn read_results(path: &std::path::Path) -> Vec<RowResult> {
+    csv::Reader::from_path(path)
+        .unwrap()
+        .deserialize()
+        .map(|r| r.unwrap())
+        .collect()
+}
+
+fn import(results: std::path::PathBuf, resume: bool) -> BillImport {
+    BillImport {
+        collection_id: Some("col1".into()),
+        callback_url: Some("https://cb.url".into()),
+        concurrency: 2,
+        results,
+        resume,
+    }
+}
+
+#[test]
+fn test_read_rows_csv_and_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(dir.path(), "bills.csv", CSV);
+    let json_path = write_file(
+        dir.path(),
+        "bills.json",
+        r#"[{"email":"a@test.com","name":"Alice","amount":10000,"description":"Invoice 1","due_at":"2024-07-12","collection_id":"col9"}]"#,
+    );
+
+    let rows: Vec<BillRow> = read_rows(&csv_path).unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].mobile, None);
+    assert_eq!(rows[1].mobile.as_deref(), Some("+60123456789"));
+
+    let rows: Vec<BillRow> = read_rows(&json_path).unwrap();
+    assert_eq!(rows[0].collection_id.as_deref(), Some("col9"));
+}
+
+#[tokio::test]
+async fn test_import_writes_results_and_resumes() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(dir.path(), "bills.csv", CSV);
+    let results = dir.path().join("bills.results.csv");
+
+    // Bob fails on the first run only.
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .and(body_partial_json(serde_json::json!({ "name": "Bob" })))
+        .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "error": { "type": "RecordInvalid", "message": ["Mobile is invalid"] }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    let summary = import(results.clone(), false)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.created, summary.failed, summary.already_created), (2, 1, 0));
+
+    let first = read_results(&results);
+    let bob = first.iter().find(|r| r.row == 2).unwrap();
+    assert_eq!(bob.status, RowStatus::Failed);
+    assert!(bob.error.as_deref().unwrap().contains("Mobile is invalid"));
+
+    let summary = import(results.clone(), true)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.created, summary.failed, summary.already_created), (1, 0, 2));
+
+    let all = read_results(&results);
+    assert_eq!(all.len(), 4);
+    let last = all.last().unwrap();
+    assert_eq!((last.row, last.status), (2, RowStatus::Created));
+    assert_eq!(last.id.as_deref(), Some("bill1"));
+}
+
+#[tokio::test]
+async fn test_import_row_without_collection_fails_locally() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(dir.path(), "bills.csv", CSV);
+    let results = dir.path().join("out.csv");
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_json("bill1")))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut settings = import(results.clone(), false);
+    settings.collection_id = None;
+    let summary = settings
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(summary.failed, 3);
+    assert!(read_results(&results)[0]
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("collection_id"));
+}
+
+#[tokio::test]
+async fn test_import_timed_out_row_is_not_resumed() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(
+        dir.path(),
+        "bills.csv",
+        "email,name,amount,description,due_at\n\
+         a@test.com,Alice,10000,Invoice 1,2024-07-12\n",
+    );
+    let results = dir.path().join("bills.results.csv");
+
+    // Billplz creates the bill, but the answer arrives after the client gave up.
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(bill_json("bill1"))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let summary = import(results.clone(), false)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.unknown, summary.failed), (1, 0));
+    assert_eq!(summary.needs_review, vec![1]);
+
+    let summary = import(results.clone(), true)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.created, summary.unknown, summary.already_created), (0, 0, 0));
+    assert_eq!(summary.needs_review, vec![1]);
+
+    let all = read_results(&results);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].status, RowStatus::Unknown);
+}
+
+const PAYOUTS_CSV: &str = "\
+bank_code,bank_account_number,identity_number,name,description,total
+MBBEMYKL,111122223333,91234567890,Alice,March salary,250000
+MBBEMYKL,444455556666,91234567891,Bob,March salary,180000
+NOT-A-BANK,777788889999,91234567892,Carol,March salary,120000
+";
+
+#[tokio::test]
+async fn test_payout_import_writes_reconciliation_report() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(dir.path(), "payouts.csv", PAYOUTS_CSV);
+    let results = dir.path().join("payouts.results.csv");
+
+    for (acc_no, status) in [("111122223333", "verified"), ("444455556666", "unverified")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v3/bank_verification_services/{}", acc_no)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "Test", "id_no": "1", "acc_no": acc_no, "code": "MBBEMYKL",
+                "organization": false, "status": status
+            })))
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/api/v4/mass_payment_instructions"))
+        .and(body_partial_json(serde_json::json!({
+            "mass_payment_instruction_collection_id": "pc1",
+            "bank_account_number": "111122223333",
+            "total": 250000
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "mpi1",
+            "mass_payment_instruction_collection_id": "pc1",
+            "bank_code": "MBBEMYKL",
+            "bank_account_number": "111122223333",
+            "identity_number": "91234567890",
+            "name": "Alice",
+            "description": "March salary",
+            "total": 250000,
+            "status": "enqueued"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let summary = PayoutImport {
+        collection_id: Some("pc1".into()),
+        concurrency: 2,
+        require_verified: true,
+        results: results.clone(),
+        resume: false,
+    }
+    .run(&client, read_rows(&csv_path).unwrap())
+    .await
+    .unwrap();
+    assert_eq!((summary.created, summary.skipped, summary.failed), (1, 1, 1));
+
+    let mut report: Vec<PayoutRowResult> = csv::Reader::from_path(&results)
+        .unwrap()
+        .deserialize()
+        .map(|r| r.unwrap())
+        .collect();
+    report.sort_by_key(|r| r.row);
+
+    assert_eq!(report[0].status, RowStatus::Created);
+    assert_eq!(report[0].id.as_deref(), Some("mpi1"));
+    assert_eq!(report[0].payout_status.as_deref(), Some("enqueued"));
+    assert_eq!(report[0].total, 250000);
+    assert_eq!(report[1].status, RowStatus::Skipped);
+    assert!(report[1].error.as_deref().unwrap().contains("unverified"));
+    assert_eq!(report[2].status, RowStatus::Failed);
+    assert!(report[2].error.as_deref().unwrap().contains("NOT-A-BANK"));
+}
+
+#[tokio::test]
+async fn test_payout_import_unanswered_row_is_not_resumed() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = write_file(
+        dir.path(),
+        "payouts.csv",
+        "bank_code,bank_account_number,identity_number,name,description,total\n\
+         MBBEMYKL,111122223333,91234567890,Alice,March salary,250000\n",
+    );
+    let results = dir.path().join("payouts.results.csv");
+    let payout_import = |resume| PayoutImport {
+        collection_id: Some("pc1".into()),
+        concurrency: 1,
+        require_verified: false,
+        results: results.clone(),
+        resume,
+    };
+
+    // The gateway times out, but the payout may still have been made.
+    Mock::given(method("POST"))
+        .and(path("/api/v4/mass_payment_instructions"))
+        .respond_with(ResponseTemplate::new(504))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let summary = payout_import(false)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.unknown, summary.failed), (1, 0));
+    assert_eq!(summary.needs_review, vec![1]);
+
+    let summary = payout_import(true)
+        .run(&client, read_rows(&csv_path).unwrap())
+        .await
+        .unwrap();
+    assert_eq!((summary.created, summary.unknown, summary.already_created), (0, 0, 0));
+    assert_eq!(summary.needs_review, vec![1]);
+
+    let report: Vec<PayoutRowResult> = csv::Reader::from_path(&results)
+        .unwrap()
+        .deserialize()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].status, RowStatus::Unknown);
+}
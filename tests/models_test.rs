@@ -1,7 +1,8 @@
 use billplz::models::{
-    bill::{Bill, BillResponse},
+    bill::{Bill, BillResponse, BillState},
     collection::{Collection, CollectionResponse, SplitPayment},
     bank::{Bank, FpxBank},
+    money::Money,
     payout::Payout,
     payout_collection::PayoutCollection,
 };
@@ -13,7 +14,7 @@ fn test_bill_serialize() {
         email: "test@test.com".to_string(),
         mobile: Some("601234567890".to_string()),
         name: "Test User".to_string(),
-        amount: 10000,
+        amount: Money::from_cents(10000),
         callback_url: "https://example.com/callback".to_string(),
         description: "Test bill".to_string(),
         due_at: "2024-07-12".to_string(),
@@ -51,9 +52,47 @@ fn test_bill_response_deserialize() {
     assert_eq!(resp.id, "bill123");
     assert!(!resp.paid);
     assert_eq!(resp.state, "due");
+    assert_eq!(resp.state, BillState::Due);
     assert_eq!(resp.amount, 10000);
 }
 
+#[test]
+fn test_bill_state_unknown_round_trips() {
+    let state: BillState = serde_json::from_str(r#""refunded""#).unwrap();
+    assert_eq!(state, BillState::Unknown("refunded".to_string()));
+    assert_eq!(serde_json::to_string(&state).unwrap(), r#""refunded""#);
+    assert_eq!(serde_json::to_string(&BillState::Paid).unwrap(), r#""paid""#);
+}
+
+#[test]
+fn test_money_display() {
+    assert_eq!(Money::from_cents(1234).to_string(), "RM 12.34");
+    assert_eq!(Money::from_cents(5).to_string(), "RM 0.05");
+    assert_eq!(Money::from_cents(-250).to_string(), "-RM 2.50");
+}
+
+#[test]
+fn test_money_parse() {
+    assert_eq!("RM 12.34".parse::<Money>().unwrap(), 1234);
+    assert_eq!("RM1,234.5".parse::<Money>().unwrap(), 123450);
+    assert_eq!("12".parse::<Money>().unwrap(), 1200);
+    assert_eq!("-RM 0.05".parse::<Money>().unwrap(), -5);
+    assert!("RM 1.234".parse::<Money>().is_err());
+    assert!("RM abc".parse::<Money>().is_err());
+    assert!("".parse::<Money>().is_err());
+}
+
+#[test]
+fn test_money_checked_arithmetic() {
+    let a = Money::from_cents(i64::MAX);
+    assert_eq!(a.checked_add(Money::from_cents(1)), None);
+    assert_eq!(
+        Money::from_cents(500).checked_sub(Money::from_cents(200)),
+        Some(Money::from_cents(300))
+    );
+    assert_eq!(Money::from_cents(250).checked_mul(3), Some(Money::from_cents(750)));
+}
+
 #[test]
 fn test_collection_serialize() {
     let collection = Collection {
@@ -73,7 +112,7 @@ fn test_collection_with_split_payments_serialize() {
         split_payments: Some(vec![
             SplitPayment {
                 email: "a@test.com".to_string(),
-                fixed_cut: Some(100),
+                fixed_cut: Some(Money::from_cents(100)),
                 variable_cut: None,
                 stack_order: 0,
             },
@@ -133,7 +172,7 @@ fn test_payout_serialize() {
         identity_number: "91234567890".to_string(),
         name: "Test User".to_string(),
         description: "Payout test".to_string(),
-        total: 50000,
+        total: Money::from_cents(50000),
     };
     let json = serde_json::to_value(&payout).unwrap();
     assert_eq!(json["total"], 50000);
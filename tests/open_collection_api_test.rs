@@ -0,0 +1,142 @@
+use billplz::BillplzClient;
+use wiremock::{MockServer, Mock, ResponseTemplate};
+use wiremock::matchers::{body_partial_json, body_string_contains, header_regex, method, path, query_param};
+
+fn open_collection_json() -> serde_json::Value {
+    serde_json::json!({
+        "id": "0pp87t_6",
+        "title": "Donation",
+        "description": "Monthly donation drive",
+        "reference_1_label": null,
+        "reference_2_label": null,
+        "email_link": null,
+        "amount": null,
+        "fixed_amount": false,
+        "tax": null,
+        "fixed_quantity": true,
+        "payment_button": "pay",
+        "photo": { "retina_url": null, "avatar_url": null },
+        "split_header": false,
+        "split_payments": [],
+        "url": "https://www.billplz.com/0pp87t_6",
+        "redirect_uri": null,
+        "status": "active"
+    })
+}
+
+#[tokio::test]
+async fn test_get_open_collection_success() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/open_collections/0pp87t_6"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(open_collection_json()))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client.get_open_collection("0pp87t_6").await.unwrap();
+    assert_eq!(resp.id, "0pp87t_6");
+    assert_eq!(resp.fixed_amount, Some(false));
+    assert!(resp.amount.is_none());
+    assert_eq!(resp.url.as_deref(), Some("https://www.billplz.com/0pp87t_6"));
+}
+
+#[tokio::test]
+async fn test_create_open_collection_open_amount() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/open_collections"))
+        .and(body_partial_json(serde_json::json!({
+            "title": "Donation",
+            "fixed_amount": false,
+            "payment_button": "pay"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(open_collection_json()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .create_open_collection("Donation", "Monthly donation drive")
+        .fixed_amount(false)
+        .payment_button("pay")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.id, "0pp87t_6");
+}
+
+#[tokio::test]
+async fn test_create_open_collection_with_photo_uses_multipart() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/open_collections"))
+        .and(header_regex("content-type", "^multipart/form-data"))
+        .and(body_string_contains("name=\"split_payments[][email]\""))
+        .and(body_string_contains("filename=\"logo.png\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(open_collection_json()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client
+        .create_open_collection("Donation", "Monthly donation drive")
+        .amount(1000)
+        .split_payment("partner@example.com", 0)
+        .photo(b"fake-png-bytes".to_vec(), "logo.png")
+        .send()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_list_open_collections() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/open_collections"))
+        .and(query_param("status", "inactive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "open_collections": [open_collection_json()],
+            "page": 1
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .list_open_collections()
+        .status("inactive")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.page, 1);
+    assert_eq!(resp.open_collections[0].id, "0pp87t_6");
+}
+
+#[tokio::test]
+async fn test_activate_and_deactivate_open_collection() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/open_collections/0pp87t_6/activate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/open_collections/0pp87t_6/deactivate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    client.activate_open_collection("0pp87t_6").await.unwrap();
+    client.deactivate_open_collection("0pp87t_6").await.unwrap();
+}
@@ -0,0 +1,122 @@
+use billplz::{BillplzClient, BillplzError};
+use futures::StreamExt;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_collections_page(mock_server: &MockServer, page: u32, ids: &[&str]) {
+    let collections: Vec<_> = ids
+        .iter()
+        .map(|id| serde_json::json!({ "id": id, "title": id, "status": "active" }))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/api/v4/collections"))
+        .and(query_param("page", page.to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "collections": collections,
+            "page": page
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_collect_all_walks_until_empty_page() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    mount_collections_page(&mock_server, 1, &["col1", "col2"]).await;
+    mount_collections_page(&mock_server, 2, &["col3"]).await;
+    mount_collections_page(&mock_server, 3, &[]).await;
+
+    let all = client.list_collections().paginate().collect_all().await.unwrap();
+    let ids: Vec<_> = all.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, ["col1", "col2", "col3"]);
+}
+
+#[tokio::test]
+async fn test_stream_is_lazy_and_honours_start_page() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    mount_collections_page(&mock_server, 2, &["col3", "col4"]).await;
+
+    let mut stream = client.list_collections().page(2).paginate();
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.id, "col3");
+    // Page 3 has not been requested; `expect(1)` on page 2 is verified on drop.
+}
+
+#[tokio::test]
+async fn test_take_pages_limits_requests() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    mount_collections_page(&mock_server, 1, &["col1"]).await;
+    mount_collections_page(&mock_server, 2, &["col2"]).await;
+
+    let all = client
+        .list_collections()
+        .paginate()
+        .take_pages(2)
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+}
+
+#[tokio::test]
+async fn test_error_ends_stream() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    mount_collections_page(&mock_server, 1, &["col1"]).await;
+    Mock::given(method("GET"))
+        .and(path("/api/v4/collections"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "error": { "type": "Unauthorized", "message": "Invalid API key" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let items: Vec<_> = client.list_collections().paginate().collect().await;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].as_ref().unwrap().id, "col1");
+    assert!(matches!(items[1], Err(BillplzError::Unauthorized { .. })));
+}
+
+#[tokio::test]
+async fn test_bill_transactions_paginate_with_status() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    for (page, ids) in [(1, vec!["t1", "t2"]), (2, vec![])] {
+        let transactions: Vec<_> = ids
+            .iter()
+            .map(|id| serde_json::json!({ "id": id, "status": "completed" }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/api/v3/bills/bill1/transactions"))
+            .and(query_param("page", page.to_string()))
+            .and(query_param("status", "completed"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "bill_id": "bill1",
+                "transactions": transactions,
+                "page": page
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let all = client
+        .get_bill_transactions("bill1")
+        .status("completed")
+        .paginate()
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+}
@@ -0,0 +1,76 @@
+use billplz::BillplzClient;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_get_payment_methods_success() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/collections/col123/payment_methods"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "payment_methods": [
+                { "code": "fpx", "name": "Online Banking", "active": true },
+                { "code": "creditcard", "name": "Credit Card", "active": false }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let methods = client.get_payment_methods("col123").await.unwrap();
+    assert_eq!(methods.len(), 2);
+    assert_eq!(methods[0].code, "fpx");
+    assert!(methods[0].active);
+    assert!(!methods[1].active);
+}
+
+#[tokio::test]
+async fn test_update_payment_methods_sends_codes() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/collections/col123/payment_methods"))
+        .and(body_json(serde_json::json!({
+            "payment_methods": [{ "code": "fpx" }, { "code": "boost" }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "payment_methods": [
+                { "code": "fpx", "name": "Online Banking", "active": true },
+                { "code": "boost", "name": "Boost", "active": true }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let methods = client
+        .update_payment_methods("col123", ["fpx", "boost"])
+        .await
+        .unwrap();
+    assert!(methods.iter().all(|m| m.active));
+}
+
+#[tokio::test]
+async fn test_get_payment_gateways_success() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/payment_gateways"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "payment_gateways": [
+                { "code": "MB2U0227", "active": true, "category": "fpx", "extras": { "isFpx": true } },
+                { "code": "BP-BOOST", "active": false, "category": "boost" }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let gateways = client.get_payment_gateways().await.unwrap();
+    assert_eq!(gateways.len(), 2);
+    assert_eq!(gateways[0].code, "MB2U0227");
+    assert_eq!(gateways[0].category.as_deref(), Some("fpx"));
+    assert!(gateways[1].extras.is_none());
+}
@@ -0,0 +1,158 @@
+use billplz::models::payment_order::{PaymentOrderCollectionStatus, PaymentOrderStatus};
+use billplz::{BillplzClient, BillplzError, XSignature};
+use wiremock::matchers::{method, path, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const KEY: &str = "S-signature-key";
+
+fn client(uri: String) -> BillplzClient {
+    BillplzClient::with_base_url(uri, "test-key").with_x_signature_key(KEY)
+}
+
+async fn last_request(mock_server: &MockServer) -> wiremock::Request {
+    mock_server.received_requests().await.unwrap().pop().unwrap()
+}
+
+#[test]
+fn test_checksum_is_hmac_sha512_of_concatenated_parts() {
+    let checksum = XSignature::new("key").checksum(["title", "", "1700000000"]);
+    assert_eq!(checksum.len(), 128);
+    assert_eq!(checksum, XSignature::new("key").checksum(["title1700000000"]));
+}
+
+#[tokio::test]
+async fn test_create_payment_order_collection_signs_body() {
+    let mock_server = MockServer::start().await;
+    let client = client(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/api/v5/payment_order_collections"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"id":"poc1","title":"March payroll","payment_orders_count":0,"paid_amount":0,"status":"active","callback_url":"https://example.com/cb"}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .create_payment_order_collection("March payroll")
+        .callback_url("https://example.com/cb")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.id, "poc1");
+    assert_eq!(resp.status, PaymentOrderCollectionStatus::Active);
+
+    let body: serde_json::Value = last_request(&mock_server).await.body_json().unwrap();
+    let epoch = body["epoch"].as_i64().unwrap().to_string();
+    let expected = XSignature::new(KEY).checksum(["March payroll", "https://example.com/cb", &epoch]);
+    assert_eq!(body["checksum"], expected);
+}
+
+#[tokio::test]
+async fn test_create_payment_order_signs_body() {
+    let mock_server = MockServer::start().await;
+    let client = client(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/api/v5/payment_orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"id":"po1","payment_order_collection_id":"poc1","bank_code":"MBBEMYKL","bank_account_number":"1234567890","name":"Ali","description":"Salary","email":"ali@example.com","status":"enqueued","notification":false,"recipient_notification":true,"reference_id":"PAY-1","display_name":"Ali","total":250000}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client
+        .create_payment_order("poc1", "MBBEMYKL", "1234567890", "Ali", "Salary", 250000)
+        .email("ali@example.com")
+        .recipient_notification(true)
+        .reference_id("PAY-1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status, PaymentOrderStatus::Enqueued);
+    assert_eq!(resp.total, 250000);
+
+    let body: serde_json::Value = last_request(&mock_server).await.body_json().unwrap();
+    assert_eq!(body["total"], 250000);
+    assert_eq!(body["reference_id"], "PAY-1");
+    assert!(body.get("notification").is_none());
+    let epoch = body["epoch"].as_i64().unwrap().to_string();
+    let expected = XSignature::new(KEY).checksum(["poc1", "1234567890", "250000", &epoch]);
+    assert_eq!(body["checksum"], expected);
+}
+
+#[tokio::test]
+async fn test_get_payment_order_signs_query() {
+    let mock_server = MockServer::start().await;
+    let client = client(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/payment_orders/po1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"id":"po1","payment_order_collection_id":"poc1","bank_code":"MBBEMYKL","bank_account_number":"1234567890","name":"Ali","description":"Salary","status":"completed","total":250000}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client.get_payment_order("po1").await.unwrap();
+    assert_eq!(resp.status, PaymentOrderStatus::Completed);
+
+    let request = last_request(&mock_server).await;
+    let query: std::collections::HashMap<_, _> = request.url.query_pairs().into_owned().collect();
+    let expected = XSignature::new(KEY).checksum(["po1", query["epoch"].as_str()]);
+    assert_eq!(query["checksum"], expected);
+}
+
+#[tokio::test]
+async fn test_get_payment_order_collection() {
+    let mock_server = MockServer::start().await;
+    let client = client(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/payment_order_collections/poc1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"id":"poc1","title":"March payroll","payment_orders_count":3,"paid_amount":750000,"status":"processing","callback_url":null}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client.get_payment_order_collection("poc1").await.unwrap();
+    assert_eq!(resp.payment_orders_count, 3);
+    assert_eq!(resp.status, PaymentOrderCollectionStatus::Processing);
+}
+
+#[tokio::test]
+async fn test_get_payment_order_limit() {
+    let mock_server = MockServer::start().await;
+    let client = client(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v5/payment_order_limit"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"total":10000000}"#))
+        .mount(&mock_server)
+        .await;
+
+    let limit = client.get_payment_order_limit().await.unwrap();
+    assert_eq!(limit.total, 10000000);
+
+    let request = last_request(&mock_server).await;
+    let query: std::collections::HashMap<_, _> = request.url.query_pairs().into_owned().collect();
+    let expected = XSignature::new(KEY).checksum(["", query["epoch"].as_str()]);
+    assert_eq!(query["checksum"], expected);
+}
+
+#[tokio::test]
+async fn test_payment_order_requires_x_signature_key() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(query_param_is_missing("checksum"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let err = client.get_payment_order_limit().await.unwrap_err();
+    assert!(matches!(err, BillplzError::Config(_)), "got {:?}", err);
+}
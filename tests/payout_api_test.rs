@@ -1,11 +1,12 @@
-use billplz::BillplzClient;
+use billplz::{BillplzClient, BillplzError};
+use billplz::models::payout::PayoutStatus;
 use wiremock::{MockServer, Mock, ResponseTemplate};
 use wiremock::matchers::{method, path};
 
 #[tokio::test]
 async fn test_get_payout_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v4/mass_payment_instructions/payout123"))
@@ -16,18 +17,20 @@ async fn test_get_payout_success() {
         .await;
 
     let resp = client.get_payout("payout123").await.unwrap();
-    assert!(resp.contains("payout123"));
+    assert_eq!(resp.id, "payout123");
+    assert_eq!(resp.status, PayoutStatus::Processing);
+    assert_eq!(resp.total, 50000);
 }
 
 #[tokio::test]
 async fn test_create_payout_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v4/mass_payment_instructions"))
         .respond_with(ResponseTemplate::new(200).set_body_string(
-            r#"{"id":"new_payout","status":"enqueued"}"#,
+            r#"{"id":"new_payout","mass_payment_instruction_collection_id":"col1","bank_code":"MBBEMYKL","bank_account_number":"999988887777","identity_number":"91234567890","name":"Test User","description":"Test payout","total":50000,"status":"enqueued"}"#,
         ))
         .mount(&mock_server)
         .await;
@@ -37,5 +40,27 @@ async fn test_create_payout_success() {
         .send()
         .await
         .unwrap();
-    assert!(resp.contains("new_payout"));
+    assert_eq!(resp.id, "new_payout");
+    assert_eq!(resp.status, PayoutStatus::Enqueued);
+}
+
+#[tokio::test]
+async fn test_get_payout_api_error() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/mass_payment_instructions/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "type": "RecordNotFound", "message": "Mass payment instruction not found" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let err = client.get_payout("missing").await.unwrap_err();
+    assert!(err.is_not_found(), "got {:?}", err);
+    assert!(matches!(
+        err,
+        BillplzError::NotFound { ref message, .. } if message == "Mass payment instruction not found"
+    ));
 }
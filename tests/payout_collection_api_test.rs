@@ -1,28 +1,31 @@
 use billplz::BillplzClient;
+use billplz::models::payout_collection::PayoutCollectionStatus;
 use wiremock::{MockServer, Mock, ResponseTemplate};
 use wiremock::matchers::{method, path};
 
 #[tokio::test]
 async fn test_get_payout_collection_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("GET"))
         .and(path("/api/v4/mass_payment_instruction_collections/pc123"))
         .respond_with(ResponseTemplate::new(200).set_body_string(
-            r#"{"id":"pc123","title":"My Payout Collection","status":"active"}"#,
+            r#"{"id":"pc123","title":"My Payout Collection","mass_payment_instructions_count":2,"paid_amount":3000,"status":"processing"}"#,
         ))
         .mount(&mock_server)
         .await;
 
     let resp = client.get_payout_collection("pc123").await.unwrap();
-    assert!(resp.contains("pc123"));
+    assert_eq!(resp.id, "pc123");
+    assert_eq!(resp.mass_payment_instructions_count, 2);
+    assert_eq!(resp.status, PayoutCollectionStatus::Processing);
 }
 
 #[tokio::test]
 async fn test_create_payout_collection_success() {
     let mock_server = MockServer::start().await;
-    let client = BillplzClient::with_base_url(&mock_server.uri(), "test-key");
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
 
     Mock::given(method("POST"))
         .and(path("/api/v4/mass_payment_instruction_collections"))
@@ -37,5 +40,31 @@ async fn test_create_payout_collection_success() {
         .send()
         .await
         .unwrap();
-    assert!(resp.contains("new_pc"));
+    assert_eq!(resp.id, "new_pc");
+    assert_eq!(resp.title, "New Payout Collection");
+}
+
+#[tokio::test]
+async fn test_list_payout_collections() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-api-key");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v4/mass_payment_instruction_collections"))
+        .and(wiremock::matchers::query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "mass_payment_instruction_collections": [
+                { "id": "pc1", "title": "Salary", "status": "completed" }
+            ],
+            "page": 1
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let resp = client.list_payout_collections().page(1).send().await.unwrap();
+    assert_eq!(resp.mass_payment_instruction_collections.len(), 1);
+    assert_eq!(
+        resp.mass_payment_instruction_collections[0].status,
+        PayoutCollectionStatus::Completed
+    );
 }
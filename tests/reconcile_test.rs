@@ -0,0 +1,147 @@
+mod common;
+
+use billplz::cli::import::read_rows;
+use billplz::models::bill::BillState;
+use billplz::reconcile::{LedgerEntry, ReconcileStatus};
+use billplz::BillplzClient;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json_with;
+
+async fn mount_bill(server: &MockServer, id: &str, paid: bool, amount: i64, paid_at: Option<&str>) {
+    let bill = bill_json_with(
+        id,
+        serde_json::json!({
+            "amount": amount,
+            "paid": paid,
+            "state": if paid { "paid" } else { "due" },
+            "paid_amount": if paid { amount } else { 0 },
+            "paid_at": paid_at
+        }),
+    );
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/bills/{}", id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill))
+        .mount(server)
+        .await;
+}
+
+fn entry(bill_id: &str, paid: bool, amount: i64) -> LedgerEntry {
+    LedgerEntry {
+        bill_id: bill_id.into(),
+        paid,
+        amount: Some(amount.into()),
+        state: None,
+        paid_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_reconcile_classifies_each_entry() {
+    let server = MockServer::start().await;
+    mount_bill(
+        &server,
+        "matched",
+        true,
+        5000,
+        Some("2024-07-10 10:00:00 +0800"),
+    )
+    .await;
+    mount_bill(
+        &server,
+        "short",
+        true,
+        4000,
+        Some("2024-07-10 10:00:00 +0800"),
+    )
+    .await;
+    mount_bill(
+        &server,
+        "paid-remote",
+        true,
+        5000,
+        Some("2024-07-10 10:00:00 +0800"),
+    )
+    .await;
+    mount_bill(&server, "paid-local", false, 5000, None).await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "type": "RecordNotFound", "message": "Bill not found" }
+        })))
+        .mount(&server)
+        .await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let mut matched = entry("matched", true, 5000);
+    matched.state = Some(BillState::Paid);
+    matched.paid_at = Some("2024-07-10".into());
+    let ledger = vec![
+        matched,
+        entry("short", true, 5000),
+        entry("paid-remote", false, 5000),
+        entry("paid-local", true, 5000),
+        entry("gone", false, 5000),
+    ];
+
+    let report = client.reconcile(ledger, 2).await;
+    let statuses: Vec<_> = report.rows.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ReconcileStatus::Matched,
+            ReconcileStatus::AmountMismatch,
+            ReconcileStatus::PaidRemotelyPendingLocally,
+            ReconcileStatus::PaidLocallyPendingRemotely,
+            ReconcileStatus::Missing,
+        ]
+    );
+    assert_eq!(report.rows[1].remote_amount, Some(4000.into()));
+    assert_eq!(report.summary.matched, 1);
+    assert_eq!(report.summary.missing, 1);
+    assert_eq!(report.discrepancies().count(), 4);
+}
+
+#[tokio::test]
+async fn test_reconcile_reports_paid_at_mismatch() {
+    let server = MockServer::start().await;
+    mount_bill(
+        &server,
+        "bill1",
+        true,
+        5000,
+        Some("2024-07-10 10:00:00 +0800"),
+    )
+    .await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let mut ledger = entry("bill1", true, 5000);
+    ledger.paid_at = Some("2024-07-11 10:00:00 +0800".into());
+    let report = client.reconcile(vec![ledger], 1).await;
+
+    assert_eq!(report.rows[0].status, ReconcileStatus::Mismatch);
+    assert!(report.rows[0]
+        .details
+        .as_deref()
+        .unwrap()
+        .starts_with("paid_at:"));
+}
+
+#[test]
+fn test_ledger_csv_with_optional_columns() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("ledger.csv");
+    std::fs::write(
+        &file,
+        "bill_id,paid,amount,state,paid_at\nbill1,true,5000,paid,2024-07-10\nbill2,false,,,\n",
+    )
+    .unwrap();
+
+    let ledger: Vec<LedgerEntry> = read_rows(&file).unwrap();
+    assert_eq!(ledger[0].amount, Some(5000.into()));
+    assert_eq!(ledger[0].state, Some(BillState::Paid));
+    assert!(!ledger[1].paid);
+    assert_eq!(ledger[1].amount, None);
+    assert_eq!(ledger[1].state, None);
+}
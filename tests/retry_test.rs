@@ -0,0 +1,184 @@
+mod common;
+
+use std::time::Duration;
+
+use billplz::{BillplzClient, BillplzError, RetryPolicy};
+use wiremock::{MockServer, Mock, ResponseTemplate};
+use wiremock::matchers::{method, path};
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .max_attempts(3)
+        .base_delay(Duration::from_millis(1))
+        .jitter(false)
+}
+
+#[tokio::test]
+async fn test_get_retries_on_server_error() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::bill_json("bill123")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let resp = client.get_bill("bill123").await.unwrap();
+    assert_eq!(resp.id, "bill123");
+}
+
+#[tokio::test]
+async fn test_get_gives_up_after_max_attempts() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    assert!(client.get_bill("bill123").await.is_err());
+}
+
+#[tokio::test]
+async fn test_honours_retry_after() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::bill_json("bill123")))
+        .mount(&mock_server)
+        .await;
+
+    let start = std::time::Instant::now();
+    client.get_bill("bill123").await.unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_post_is_not_retried() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = client
+        .create_bill("col1", "test@test.com", "Test", 5000, "https://cb.url", "Test", "2024-07-12")
+        .send()
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_post_with_idempotency_key_is_retried_once_per_key() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::bill_json("bill1")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // The first send is retried after the 503; the second is answered from the store.
+    for _ in 0..2 {
+        let bill = client
+            .create_bill("col1", "test@test.com", "Test", 5000, "https://cb.url", "Test", "2024-07-12")
+            .idempotency_key("inv-42")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(bill.id, "bill1");
+    }
+}
+
+#[tokio::test]
+async fn test_timed_out_post_is_not_retried() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::builder()
+        .base_url(mock_server.uri())
+        .api_key("test-key")
+        .timeout(Duration::from_millis(100))
+        .retry_policy(fast_policy())
+        .build()
+        .unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v3/bills"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(common::bill_json("bill1"))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = client
+        .create_bill("col1", "test@test.com", "Test", 5000, "https://cb.url", "Test", "2024-07-12")
+        .idempotency_key("inv-42")
+        .send()
+        .await;
+    assert!(matches!(result, Err(BillplzError::Http(e)) if e.is_timeout()));
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key")
+        .with_retry_policy(fast_policy());
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "type": "RecordNotFound", "message": "Bill not found" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    assert!(client.get_bill("missing").await.is_err());
+}
+
+#[test]
+fn test_retry_policy_none() {
+    assert_eq!(RetryPolicy::none().max_attempts, 1);
+    let client = BillplzClient::with_base_url("http://localhost", "key");
+    assert_eq!(client.retry_policy().max_attempts, 3);
+}
@@ -0,0 +1,113 @@
+use billplz::{BillplzError, XSignature};
+
+const KEY: &str = "S-s7b4ATnEc4PhAx0kL1d3Qg";
+const CALLBACK_SIGNATURE: &str = "1db5f811965190b061994aabbe39c46dfab15d7a4350778ce3d8a520e80d7aa7";
+const REDIRECT_SIGNATURE: &str = "eff3db985f56bcedd98de1d8917ae9e0db5013ca087dc96c271e7e0021c9cd70";
+
+fn callback_params() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("id", "W_79pJDk"),
+        ("collection_id", "599"),
+        ("paid", "true"),
+        ("state", "paid"),
+        ("amount", "200"),
+        ("paid_amount", "200"),
+        ("due_at", "2020-12-31"),
+        ("email", "api@billplz.com"),
+        ("mobile", "+60112223333"),
+        ("name", "MICHAEL API"),
+        ("url", "http://www.billplz.com/bills/W_79pJDk"),
+        ("paid_at", "2015-03-09 16:23:59 +0800"),
+    ]
+}
+
+#[test]
+fn test_source_string_is_sorted() {
+    let source = XSignature::source_string(callback_params());
+    assert_eq!(
+        source,
+        "amount200|collection_id599|due_at2020-12-31|emailapi@billplz.com|idW_79pJDk|mobile+60112223333|nameMICHAEL API|paid_amount200|paid_at2015-03-09 16:23:59 +0800|paidtrue|statepaid|urlhttp://www.billplz.com/bills/W_79pJDk"
+    );
+}
+
+#[test]
+fn test_sign_callback() {
+    let signer = XSignature::new(KEY);
+    assert_eq!(signer.sign(callback_params()), CALLBACK_SIGNATURE);
+}
+
+#[test]
+fn test_verify_callback_success() {
+    let signer = XSignature::new(KEY);
+    let mut params = callback_params();
+    params.push(("x_signature", CALLBACK_SIGNATURE));
+
+    let payload = signer.verify_callback(params).unwrap();
+    assert_eq!(payload.id, "W_79pJDk");
+    assert!(payload.paid);
+    assert_eq!(payload.amount, 200);
+    assert_eq!(payload.paid_at.as_deref(), Some("2015-03-09 16:23:59 +0800"));
+}
+
+#[test]
+fn test_verify_callback_tampered() {
+    let signer = XSignature::new(KEY);
+    let mut params = callback_params();
+    params[4] = ("amount", "100");
+    params.push(("x_signature", CALLBACK_SIGNATURE));
+
+    let err = signer.verify_callback(params).unwrap_err();
+    assert!(matches!(err, BillplzError::InvalidSignature));
+}
+
+#[test]
+fn test_verify_callback_missing_signature() {
+    let signer = XSignature::new(KEY);
+    let err = signer.verify_callback(callback_params()).unwrap_err();
+    assert!(matches!(err, BillplzError::InvalidSignature));
+
+    let mut params = callback_params();
+    params.push(("x_signature", ""));
+    let err = signer.verify_callback(params).unwrap_err();
+    assert!(matches!(err, BillplzError::InvalidSignature));
+}
+
+#[test]
+fn test_verify_callback_form() {
+    let signer = XSignature::new(KEY);
+    let mut params = callback_params();
+    params.push(("x_signature", CALLBACK_SIGNATURE));
+    let body: String = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+
+    let payload = signer.verify_callback_form(&body).unwrap();
+    assert_eq!(payload.collection_id, "599");
+}
+
+#[test]
+fn test_verify_redirect_query() {
+    let signer = XSignature::new(KEY);
+    let query = format!(
+        "?billplz%5Bid%5D=W_79pJDk&billplz%5Bpaid%5D=true&billplz%5Bpaid_at%5D=2015-03-09+16%3A23%3A59+%2B0800&billplz%5Bx_signature%5D={}",
+        REDIRECT_SIGNATURE
+    );
+
+    let payload = signer.verify_redirect_query(&query).unwrap();
+    assert_eq!(payload.id, "W_79pJDk");
+    assert!(payload.paid);
+}
+
+#[test]
+fn test_verify_redirect_wrong_key() {
+    let signer = XSignature::new("wrong-key");
+    let params = vec![
+        ("billplz[id]", "W_79pJDk"),
+        ("billplz[paid]", "true"),
+        ("billplz[paid_at]", "2015-03-09 16:23:59 +0800"),
+        ("billplz[x_signature]", REDIRECT_SIGNATURE),
+    ];
+
+    let err = signer.verify_redirect(params).unwrap_err();
+    assert!(matches!(err, BillplzError::InvalidSignature));
+}
@@ -0,0 +1,244 @@
+use billplz::models::bank::BankVerificationStatus;
+use billplz::models::bill::BillState;
+use billplz::models::payout::PayoutStatus;
+use billplz::{BillplzClient, BillplzError, Simulator, XSignature};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "sim-api-key";
+const X_SIGNATURE_KEY: &str = "sim-signature-key";
+
+async fn start() -> (String, BillplzClient) {
+    let base_url = Simulator::new(API_KEY)
+        .x_signature_key(X_SIGNATURE_KEY)
+        .spawn()
+        .await
+        .unwrap();
+    let client = BillplzClient::builder()
+        .base_url(&base_url)
+        .api_key(API_KEY)
+        .build()
+        .unwrap();
+    (base_url, client)
+}
+
+async fn admin(base_url: &str, path: &str, body: serde_json::Value) -> serde_json::Value {
+    reqwest::Client::new()
+        .post(format!("{}{}", base_url, path))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_paying_a_bill_sends_signed_callback() {
+    let receiver = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/callback"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&receiver)
+        .await;
+
+    let (base_url, client) = start().await;
+    let collection = client.create_collection("Sim").send().await.unwrap();
+    let bill = client
+        .create_bill(
+            &collection.id,
+            "api@billplz.com",
+            "Michael",
+            2000,
+            format!("{}/callback", receiver.uri()),
+            "Test bill",
+            "2030-12-31",
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bill.state, BillState::Due);
+    assert_eq!(
+        bill.url.as_deref(),
+        Some(format!("{}/bills/{}", base_url, bill.id).as_str())
+    );
+
+    let paid = admin(
+        &base_url,
+        &format!("/sim/bills/{}/pay", bill.id),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(paid["callback"]["status"], 200);
+
+    let fetched = client.get_bill(&bill.id).await.unwrap();
+    assert!(fetched.paid);
+    assert_eq!(fetched.state, BillState::Paid);
+    assert_eq!(fetched.paid_amount, 2000);
+
+    let transactions = client.get_bill_transactions(&bill.id).send().await.unwrap();
+    assert_eq!(transactions.transactions.len(), 1);
+    assert_eq!(transactions.transactions[0].status, "completed");
+
+    let requests = receiver.received_requests().await.unwrap();
+    let form = String::from_utf8(requests[0].body.clone()).unwrap();
+    let payload = XSignature::new(X_SIGNATURE_KEY)
+        .verify_callback_form(&form)
+        .unwrap();
+    assert_eq!(payload.id, bill.id);
+    assert!(payload.paid);
+    assert_eq!(payload.transaction_status.as_deref(), Some("completed"));
+}
+
+#[tokio::test]
+async fn test_wrong_api_key_is_unauthorized() {
+    let (base_url, _) = start().await;
+    let client = BillplzClient::builder()
+        .base_url(&base_url)
+        .api_key("wrong-key")
+        .build()
+        .unwrap();
+
+    let err = client.get_collection("anything").await.unwrap_err();
+    assert!(matches!(err, BillplzError::Unauthorized { .. }));
+}
+
+#[tokio::test]
+async fn test_bill_rules_are_enforced() {
+    let (_, client) = start().await;
+
+    let err = client.get_bill("missing").await.unwrap_err();
+    assert!(matches!(err, BillplzError::NotFound { .. }));
+
+    let collection = client.create_collection("Sim").send().await.unwrap();
+    client.deactivate_collection(&collection.id).await.unwrap();
+    let err = client
+        .create_bill(
+            &collection.id,
+            "a@b.com",
+            "A",
+            100,
+            "http://localhost/cb",
+            "d",
+            "2030-01-01",
+        )
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BillplzError::Validation { .. }));
+
+    client.activate_collection(&collection.id).await.unwrap();
+    let bill = client
+        .create_bill(
+            &collection.id,
+            "a@b.com",
+            "A",
+            100,
+            "http://localhost/cb",
+            "d",
+            "2030-01-01",
+        )
+        .send()
+        .await
+        .unwrap();
+    client.delete_bill(&bill.id).await.unwrap();
+    assert_eq!(
+        client.get_bill(&bill.id).await.unwrap().state,
+        BillState::Deleted
+    );
+}
+
+#[tokio::test]
+async fn test_collections_are_listed_in_pages() {
+    let (_, client) = start().await;
+    for i in 0..20 {
+        client
+            .create_collection(format!("Collection {}", i))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let first = client.list_collections().send().await.unwrap();
+    assert_eq!(first.collections.len(), 15);
+    let all = client
+        .list_collections()
+        .paginate()
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 20);
+}
+
+#[tokio::test]
+async fn test_payout_flow() {
+    let (base_url, client) = start().await;
+
+    let verification = client
+        .create_bank_verification("Ali", "910101011234", "1234567890", "MBBEMYKL")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(verification.status, BankVerificationStatus::Pending);
+    admin(
+        &base_url,
+        "/sim/bank_verifications/1234567890",
+        serde_json::json!({ "status": "verified" }),
+    )
+    .await;
+    let verification = client.get_bank_verification("1234567890").await.unwrap();
+    assert_eq!(verification.status, BankVerificationStatus::Verified);
+
+    let collection = client
+        .create_payout_collection("Salaries")
+        .send()
+        .await
+        .unwrap();
+    let payout = client
+        .create_payout(
+            &collection.id,
+            "MBBEMYKL",
+            "1234567890",
+            "910101011234",
+            "Ali",
+            "Salary",
+            5000,
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(payout.status, PayoutStatus::Enqueued);
+
+    admin(
+        &base_url,
+        &format!("/sim/payouts/{}", payout.id),
+        serde_json::json!({ "status": "completed" }),
+    )
+    .await;
+    assert_eq!(
+        client.get_payout(&payout.id).await.unwrap().status,
+        PayoutStatus::Completed
+    );
+
+    let collection = client.get_payout_collection(&collection.id).await.unwrap();
+    assert_eq!(collection.mass_payment_instructions_count, 1);
+    assert_eq!(collection.paid_amount, 5000);
+
+    let err = client
+        .create_payout(
+            &collection.id,
+            "NOTABANK",
+            "1234567890",
+            "910101011234",
+            "Ali",
+            "Salary",
+            5000,
+        )
+        .validate_on_send(false)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BillplzError::Validation { .. }));
+}
@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use billplz::transport::{HttpRequest, HttpResponse, MockTransport, TransportFuture};
+use billplz::{BillplzClient, BillplzError, RetryPolicy, Transport};
+use reqwest::{Method, StatusCode};
+
+fn mock_client(transport: &MockTransport) -> BillplzClient {
+    BillplzClient::builder()
+        .base_url("https://www.billplz-sandbox.com")
+        .api_key("test-key")
+        .transport(transport.clone())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_mock_transport_routes_by_method_and_path() {
+    let transport = MockTransport::new();
+    transport.on(
+        Method::GET,
+        "/api/v4/collections/col1",
+        200,
+        r#"{"id":"col1","title":"Mocked","status":"active"}"#,
+    );
+    let client = mock_client(&transport);
+
+    let resp = client.get_collection("col1").await.unwrap();
+    assert_eq!(resp.title, "Mocked");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(requests[0].headers["authorization"], "Basic dGVzdC1rZXk6");
+}
+
+#[tokio::test]
+async fn test_mock_transport_records_json_body() {
+    let transport = MockTransport::new();
+    transport.push_response(200, r#"{"id":"new_col","title":"New","status":"active"}"#);
+    let client = mock_client(&transport);
+
+    client.create_collection("New").send().await.unwrap();
+
+    let body: serde_json::Value =
+        serde_json::from_str(&transport.requests()[0].body_text().unwrap()).unwrap();
+    assert_eq!(body["title"], "New");
+}
+
+#[tokio::test]
+async fn test_mock_transport_unmatched_request_is_not_found() {
+    let transport = MockTransport::new();
+    let client = mock_client(&transport);
+
+    let err = client.get_bill("missing").await.unwrap_err();
+    assert!(matches!(err, BillplzError::NotFound { .. }), "got {:?}", err);
+}
+
+#[tokio::test]
+async fn test_mock_transport_queue_drives_retries() {
+    let transport = MockTransport::new();
+    transport.push_response(503, "");
+    transport.push_response(200, r#"{"id":"col1","title":"Recovered","status":"active"}"#);
+    let client = BillplzClient::builder()
+        .base_url("https://www.billplz-sandbox.com")
+        .api_key("test-key")
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy::default().base_delay(std::time::Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let resp = client.get_collection("col1").await.unwrap();
+    assert_eq!(resp.title, "Recovered");
+    assert_eq!(transport.requests().len(), 2);
+}
+
+struct CountingTransport {
+    calls: Arc<AtomicUsize>,
+}
+
+impl Transport for CountingTransport {
+    fn send(&self, _request: HttpRequest) -> TransportFuture<'_> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async {
+            Ok(HttpResponse::new(
+                StatusCode::OK,
+                r#"{"id":"col1","title":"Counted","status":"active"}"#,
+            ))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_transport() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let client = BillplzClient::builder()
+        .environment(billplz::Environment::Staging)
+        .api_key("test-key")
+        .transport(CountingTransport { calls: calls.clone() })
+        .build()
+        .unwrap();
+
+    client.get_collection("col1").await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_transport_rejects_http_options() {
+    let err = BillplzClient::builder()
+        .environment(billplz::Environment::Staging)
+        .api_key("test-key")
+        .transport(MockTransport::new())
+        .timeout(std::time::Duration::from_secs(1))
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, BillplzError::Config(_)));
+}
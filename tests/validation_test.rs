@@ -0,0 +1,194 @@
+use billplz::{BillplzClient, BillplzError, Environment};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client() -> BillplzClient {
+    BillplzClient::with_base_url("http://127.0.0.1:9", "test-key")
+}
+
+fn fields(result: Result<(), billplz::ValidationError>) -> Vec<String> {
+    result
+        .unwrap_err()
+        .errors
+        .into_iter()
+        .map(|e| e.field)
+        .collect()
+}
+
+#[test]
+fn test_bill_validate_lists_every_failing_field() {
+    let client = client();
+    let builder = client
+        .create_bill(
+            "col1",
+            "not-an-email",
+            "Ali",
+            99,
+            "https://example.com/cb",
+            "x".repeat(201),
+            "31/12/2024",
+        )
+        .mobile("0123456789")
+        .reference_1("r".repeat(121));
+
+    assert_eq!(
+        fields(builder.validate()),
+        vec![
+            "mobile",
+            "email",
+            "amount",
+            "description",
+            "due_at",
+            "reference_1"
+        ]
+    );
+}
+
+#[test]
+fn test_bill_validate_accepts_mobile_without_email() {
+    let client = client();
+    let builder = client
+        .create_bill(
+            "col1",
+            "",
+            "Ali",
+            100,
+            "https://example.com/cb",
+            "Invoice",
+            "2024-12-31",
+        )
+        .mobile("60123456789");
+    assert!(builder.validate().is_ok());
+
+    let builder = client.create_bill(
+        "col1",
+        "",
+        "Ali",
+        100,
+        "https://example.com/cb",
+        "Invoice",
+        "",
+    );
+    assert_eq!(fields(builder.validate()), vec!["email"]);
+}
+
+#[test]
+fn test_collection_validate() {
+    let client = client();
+    let builder = client
+        .create_collection("t".repeat(51))
+        .split_payment("partner", 0)
+        .split_payment_with_variable_cut("partner@example.com", "120", 1);
+
+    assert_eq!(
+        fields(builder.validate()),
+        vec![
+            "title",
+            "split_payments[0].email",
+            "split_payments[1].variable_cut"
+        ]
+    );
+}
+
+#[test]
+fn test_payout_and_bank_verification_validate() {
+    let client = client();
+    let payout = client.create_payout("pc1", "MBB", "12-34", "", "Ali", "Salary", 0);
+    assert_eq!(
+        fields(payout.validate()),
+        vec![
+            "bank_code",
+            "bank_account_number",
+            "total",
+            "identity_number"
+        ]
+    );
+
+    let verification = client.create_bank_verification("", "910101011234", "12345", "MBBEMYKL");
+    assert_eq!(fields(verification.validate()), vec!["name", "acc_no"]);
+}
+
+#[test]
+fn test_bank_code_is_checked_for_format_only() {
+    let client = BillplzClient::builder()
+        .environment(Environment::Production)
+        .api_key("test-key")
+        .build()
+        .unwrap();
+    let payout = |code: &str| {
+        client
+            .create_payout("pc1", code, "111122223333", "910101011234", "Ali", "Salary", 100)
+            .validate()
+    };
+
+    // A bank missing from the built-in table is left for Billplz to judge.
+    for code in ["MBBEMYKL", "NEWBMYKL", "MBBEMYKLXXX"] {
+        assert!(payout(code).is_ok(), "{} should be accepted", code);
+    }
+    for code in ["mbbemykl", "MBB", "MBBEMYKLX", "MBB-MYKL"] {
+        assert_eq!(fields(payout(code)), vec!["bank_code"], "{} should be rejected", code);
+    }
+    let verification = client.create_bank_verification("Ali", "910101011234", "111122223333", "MBBE MYKL");
+    assert_eq!(fields(verification.validate()), vec!["code"]);
+}
+
+#[tokio::test]
+async fn test_send_validates_before_any_request() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let err = client
+        .create_bill(
+            "col1",
+            "nope",
+            "Ali",
+            10,
+            "https://example.com/cb",
+            "Invoice",
+            "2024-12-31",
+        )
+        .send()
+        .await
+        .unwrap_err();
+    match err {
+        BillplzError::InvalidRequest(e) => {
+            assert!(e.has_field("email") && e.has_field("amount"));
+            assert_eq!(
+                e.to_string(),
+                "email \"nope\" is not a valid email address; amount must be at least 100 sen, got 10"
+            );
+        }
+        other => panic!("expected InvalidRequest, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_validate_on_send_false_skips_checks() {
+    let mock_server = MockServer::start().await;
+    let client = BillplzClient::with_base_url(mock_server.uri(), "test-key");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v4/collections"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "col1",
+            "title": "",
+            "status": "active"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let collection = client
+        .create_collection("")
+        .validate_on_send(false)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(collection.id, "col1");
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::time::Duration;
+
+use billplz::models::bill::BillState;
+use billplz::{BillplzClient, BillplzError, WaitOptions, MIN_POLL_INTERVAL};
+use futures::StreamExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::bill_json_with;
+
+fn bill_body(id: &str, state: &str, due_at: &str) -> serde_json::Value {
+    let paid = state == "paid";
+    bill_json_with(
+        id,
+        serde_json::json!({
+            "due_at": due_at,
+            "paid": paid,
+            "state": state,
+            "paid_amount": if paid { 10000 } else { 0 }
+        }),
+    )
+}
+
+/// Responds `due` for the first `due_polls` requests, then with `then`.
+async fn mount_bill(server: &MockServer, id: &str, due_polls: u64, then: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/bills/{}", id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_body(id, "due", "2999-12-31")))
+        .up_to_n_times(due_polls)
+        .with_priority(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v3/bills/{}", id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_body(id, then, "2999-12-31")))
+        .mount(server)
+        .await;
+}
+
+fn fast() -> WaitOptions {
+    WaitOptions::default()
+        .timeout(Duration::from_secs(5))
+        .interval(Duration::from_millis(10))
+        .max_interval(Duration::from_millis(20))
+}
+
+#[tokio::test]
+async fn test_wait_for_bill_returns_once_paid() {
+    let server = MockServer::start().await;
+    mount_bill(&server, "bill1", 2, "paid").await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let bill = client.wait_for_bill("bill1", fast()).await.unwrap();
+    assert!(bill.paid);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_wait_for_bill_accepts_huge_timeout() {
+    let server = MockServer::start().await;
+    mount_bill(&server, "bill1", 1, "paid").await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let options = fast().timeout(Duration::MAX).max_interval(Duration::MAX);
+    let bill = client.wait_for_bill("bill1", options).await.unwrap();
+    assert!(bill.paid);
+}
+
+#[tokio::test]
+async fn test_wait_for_bill_stops_at_past_due_date() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/bill1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bill_body(
+            "bill1",
+            "due",
+            "2020-01-01",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let bill = client.wait_for_bill("bill1", fast()).await.unwrap();
+    assert!(!bill.paid);
+    assert_eq!(bill.state, BillState::Due);
+}
+
+#[tokio::test]
+async fn test_wait_for_bill_times_out() {
+    let server = MockServer::start().await;
+    mount_bill(&server, "bill1", u64::MAX, "paid").await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let err = client
+        .wait_for_bill("bill1", fast().timeout(Duration::from_millis(50)))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BillplzError::WaitTimeout { ref bill_id, ref state } if bill_id == "bill1" && state == "due"
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_bill_zero_interval_does_not_spin() {
+    let server = MockServer::start().await;
+    mount_bill(&server, "bill1", u64::MAX, "paid").await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let options = WaitOptions::default()
+        .timeout(MIN_POLL_INTERVAL * 3)
+        .interval(Duration::ZERO);
+    let err = client.wait_for_bill("bill1", options).await.unwrap_err();
+    assert!(matches!(err, BillplzError::WaitTimeout { .. }));
+    assert!(server.received_requests().await.unwrap().len() <= 5);
+}
+
+#[tokio::test]
+async fn test_watch_bills_emits_state_changes() {
+    let server = MockServer::start().await;
+    mount_bill(&server, "bill1", 2, "paid").await;
+    mount_bill(&server, "bill2", 1, "deleted").await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let events: Vec<_> = client
+        .watch_bills(["bill1", "bill2"])
+        .options(fast())
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let seen: Vec<_> = events
+        .iter()
+        .map(|e| {
+            (
+                e.bill.id.as_str(),
+                e.previous.clone(),
+                e.bill.state.clone(),
+                e.settled,
+            )
+        })
+        .collect();
+    assert_eq!(
+        seen,
+        vec![
+            ("bill1", None, BillState::Due, false),
+            ("bill2", None, BillState::Due, false),
+            ("bill2", Some(BillState::Due), BillState::Deleted, true),
+            ("bill1", Some(BillState::Due), BillState::Paid, true),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_watch_bills_reports_fetch_errors_once() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/bills/gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": { "type": "RecordNotFound", "message": "Bill not found" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = BillplzClient::with_base_url(server.uri(), "test-key");
+
+    let events: Vec<_> = client.watch_bills(["gone"]).options(fast()).collect().await;
+    assert_eq!(events.len(), 1);
+    assert!(events[0].as_ref().unwrap_err().is_not_found());
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use billplz::cli::webhook::WebhookListener;
+use tokio::net::TcpListener;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{callback_form, CALLBACK_SIGNATURE, KEY};
+
+async fn spawn(listener: WebhookListener) -> String {
+    let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = listener.serve(tcp).await;
+    });
+    format!("http://{}", addr)
+}
+
+async fn post_form(url: &str, body: String) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(url)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(body)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_verified_callback_is_forwarded() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/billplz/callback"))
+        .and(body_string_contains("x_signature=".to_string() + CALLBACK_SIGNATURE))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let url = spawn(
+        WebhookListener::new(KEY).forward_to(format!("{}/billplz/callback", mock_server.uri())),
+    )
+    .await;
+
+    let resp = post_form(&url, callback_form(CALLBACK_SIGNATURE)).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+async fn test_invalid_signature_is_rejected() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let url = spawn(WebhookListener::new(KEY).forward_to(mock_server.uri())).await;
+
+    let resp = post_form(&url, callback_form("not-a-signature")).await;
+    assert_eq!(resp.status(), 401);
+}
+
+#[tokio::test]
+async fn test_unsigned_callback_is_unauthorized() {
+    let url = spawn(WebhookListener::new(KEY)).await;
+
+    let resp = post_form(&url, "id=W_79pJDk".to_string()).await;
+    assert_eq!(resp.status(), 401);
+}
+
+#[tokio::test]
+async fn test_undecodable_callback_is_bad_request() {
+    let url = spawn(WebhookListener::new(KEY)).await;
+
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(vec![0xff, 0xfe])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test]
+async fn test_non_post_is_rejected() {
+    let url = spawn(WebhookListener::new(KEY)).await;
+
+    let resp = reqwest::get(&url).await.unwrap();
+    assert_eq!(resp.status(), 405);
+}